@@ -0,0 +1,145 @@
+/**
+ * DegenScore Event Test Helpers
+ *
+ * Shared by the programs' event tests. Events are read back from
+ * "Program data:" log lines and decoded the same way the indexer does.
+ *
+ * Two ways to get those lines:
+ * - `capture_logs` runs a closure calling `emit!` directly, under a syscall
+ *   stub that records what it logs
+ * - `record_program_logs` keeps the stubs solana-program-test installs (CPIs,
+ *   sysvars) and additionally records what programs log while it executes
+ *   real instructions; `program_logs` returns everything recorded so far
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::Event;
+use std::sync::{Mutex, Once};
+
+static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static CAPTURE: Mutex<()> = Mutex::new(());
+static PROGRAM_LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn program_data_line(fields: &[&[u8]]) -> String {
+    let encoded: Vec<String> = fields.iter().map(base64::encode).collect();
+    format!("Program data: {}", encoded.join(" "))
+}
+
+struct LogCapture;
+
+impl SyscallStubs for LogCapture {
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        LOGS.lock().unwrap().push(program_data_line(fields));
+    }
+}
+
+/// Run `f` and return the program log lines it produced
+pub fn capture_logs(f: impl FnOnce()) -> Vec<String> {
+    let _guard = CAPTURE.lock().unwrap();
+    set_syscall_stubs(Box::new(LogCapture));
+    LOGS.lock().unwrap().clear();
+    f();
+    LOGS.lock().unwrap().drain(..).collect()
+}
+
+/// Forwards every syscall to the stubs it replaced, recording `sol_log_data`
+struct ProgramLogRecorder {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for ProgramLogRecorder {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+    fn sol_invoke_signed(&self, instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        self.inner.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memcpy(dst, src, n)
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memmove(dst, src, n)
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.inner.sol_memcmp(s1, s2, n, result)
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.inner.sol_memset(s, c, n)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        PROGRAM_LOGS.lock().unwrap().push(program_data_line(fields));
+        self.inner.sol_log_data(fields)
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+}
+
+/// Start recording the events of programs run by solana-program-test. Call it
+/// once the test validator has started, as that installs its own stubs.
+///
+/// Tests in the same binary share the recording, so look events up by
+/// something unique to the test (a fresh owner or mint).
+pub fn record_program_logs() {
+    static RECORDING: Once = Once::new();
+    RECORDING.call_once(|| {
+        // Swap in a placeholder to take ownership of the current stubs
+        let inner = set_syscall_stubs(Box::new(LogCapture));
+        set_syscall_stubs(Box::new(ProgramLogRecorder { inner }));
+    });
+}
+
+/// Every "Program data:" line recorded since `record_program_logs`
+pub fn program_logs() -> Vec<String> {
+    PROGRAM_LOGS.lock().unwrap().clone()
+}
+
+/// Decode every `T` event in the logs, in order
+pub fn decode_events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| base64::decode(data).ok())
+        .filter(|bytes| bytes.starts_with(&T::DISCRIMINATOR))
+        .map(|bytes| T::deserialize(&mut &bytes[8..]).unwrap())
+        .collect()
+}
+
+/// Find and decode the first `T` event in the logs
+pub fn decode_event<T: Event>(logs: &[String]) -> Option<T> {
+    decode_events(logs).into_iter().next()
+}
+
+/// Anchor's event discriminator: the first 8 bytes of sha256("event:<name>")
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("event:{}", name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
@@ -0,0 +1,52 @@
+/**
+ * $DEGEN Staking Interface
+ *
+ * Read-only mirror of the staking program's per-wallet summary, so other
+ * programs can grant perks by staking tier without depending on the staking
+ * program itself. The staking tests check that this layout matches the
+ * account the program writes.
+ *
+ * Reading a wallet's tier:
+ * - Take `Option<Account<'info, StakerSummary>>` with
+ *   `seeds = [STAKER_SUMMARY_SEED, owner.key().as_ref()]` and
+ *   `seeds::program = staking_interface::ID`; Anchor checks the discriminator
+ *   and that the staking program owns the account
+ * - `tier_of` maps a missing summary (never staked) to `Tier::None`
+ */
+
+use anchor_lang::prelude::*;
+
+// The staking program's id: `Account<StakerSummary>` checks the owner against it
+declare_id!("DegenStake1111111111111111111111111111111");
+
+// StakerSummary PDA: [STAKER_SUMMARY_SEED, owner]
+pub const STAKER_SUMMARY_SEED: &[u8] = b"staker";
+
+/// Address of `owner`'s summary account
+pub fn staker_summary_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STAKER_SUMMARY_SEED, owner.as_ref()], &ID).0
+}
+
+/// Staking tier of a wallet, `Tier::None` when it has never staked
+pub fn tier_of(staker_summary: Option<&StakerSummary>) -> Tier {
+    staker_summary.map_or(Tier::None, |summary| summary.tier)
+}
+
+/// Same name and fields as the staking program's `StakerSummary`, so the
+/// discriminator and borsh layout match
+#[account]
+pub struct StakerSummary {
+    pub owner: Pubkey,
+    pub total_amount: u64,
+    pub open_positions: u32,
+    pub next_position_index: u64,
+    pub tier: Tier,
+}
+
+/// Same variants, in the same order, as the staking program's `Tier`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Tier {
+    None,
+    Staker,
+    Whale,
+}
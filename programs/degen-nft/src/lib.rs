@@ -4,24 +4,76 @@
  * Dynamic NFTs that represent a trader's DegenScore Card on-chain.
  *
  * Features:
- * - Mint NFTs with dynamic metadata
- * - Update scores on-chain
+ * - Mint NFTs with dynamic metadata (Metaplex metadata + master edition), each
+ *   a verified member of the DegenScore collection once it has been created
+ * - Update scores on-chain (oracle-attested), refreshing the metadata URI
+ * - On-chain score history per card
+ * - Staking perks: Staker/Whale wallets get a tier flag on their card, and
+ *   shorter waits between score updates once the authority sets a limit
+ * - Mint price in $DEGEN, paid through `transfer_with_fees` so the burn and
+ *   treasury split applies; discounted by staking tier, free for Genesis cards
+ * - Tradeable or soulbound, chosen at mint: soulbound cards stay frozen in the
+ *   owner's wallet and move to a new wallet only through `burn_and_remint`
  * - Composable with other protocols
- * - 5% royalties to treasury
- * - Tradeable on all NFT marketplaces
+ * - 5% royalties to treasury (changes are timelocked)
  */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked};
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use mpl_token_metadata::instruction::{create_master_edition_v3, create_metadata_accounts_v3, update_metadata_accounts_v2};
+use degen_token::program::DegenToken;
+use degen_token::{Exemption, TokenData};
+use mpl_token_metadata::instruction::{
+    burn_nft, create_master_edition_v3, create_metadata_accounts_v3, freeze_delegated_account, thaw_delegated_account,
+    update_metadata_accounts_v2, verify_collection,
+};
+use mpl_token_metadata::pda::find_metadata_account;
+use mpl_token_metadata::state::{
+    Collection, Creator, DataV2, Metadata, TokenMetadataAccount, MAX_URI_LENGTH,
+};
+use staking_interface::{StakerSummary, Tier, STAKER_SUMMARY_SEED};
 
 declare_id!("DegenNFT11111111111111111111111111111111111");
 
+// Oracle attestations older than this are rejected (5 minutes)
+const MAX_ATTESTATION_AGE: i64 = 300;
+
+// Tolerated oracle clock drift ahead of the cluster clock. A far-future
+// attestation would otherwise block every later update of the card.
+const MAX_ATTESTATION_SKEW: i64 = 60;
+
+// Number of score snapshots kept in each card's history ring buffer
+pub const SCORE_HISTORY_LEN: usize = 32;
+
+// Metaplex symbol shared by every card
+const CARD_SYMBOL: &str = "DEGEN";
+
+// Metaplex name of the collection NFT grouping every card
+const COLLECTION_NAME: &str = "DegenScore Cards";
+
+// The first cards minted are Genesis cards, minted for free
+pub const GENESIS_SUPPLY: u64 = 1000;
+
+// Default mint price discounts by staking tier (basis points): None / Staker / Whale
+const DEFAULT_TIER_DISCOUNTS_BP: [u16; 3] = [0, 5000, 10000];
+
+// Default minimum time between score updates by staking tier: None / Staker / Whale.
+// No limit until the authority sets one with `set_update_intervals`.
+const DEFAULT_UPDATE_INTERVALS: [i64; 3] = [0, 0, 0];
+
+// Royalty changes wait this long between queueing and execution (48 hours)
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 48 * 3600;
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 3600;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 3600;
+
 #[program]
 pub mod degen_nft {
     use super::*;
 
-    /// Mint a new DegenScore NFT
+    /// Mint a new DegenScore NFT, either tradeable or soulbound (`mode`)
     pub fn mint_degen_card(
         ctx: Context<MintDegenCard>,
         degen_score: u8,
@@ -29,9 +81,17 @@ pub mod degen_nft {
         total_volume: u64,
         win_rate: u8,
         uri: String,
+        mode: CardMode,
     ) -> Result<()> {
         require!(degen_score <= 100, ErrorCode::InvalidScore);
         require!(win_rate <= 100, ErrorCode::InvalidWinRate);
+        require!(uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+
+        let staking_tier = staking_interface::tier_of(ctx.accounts.staker_summary.as_deref());
+        let price = ctx.accounts.global_state.mint_price_for(staking_tier);
+        if price > 0 {
+            pay_mint_price(ctx.accounts, price)?;
+        }
 
         let card_data = &mut ctx.accounts.card_data;
         card_data.owner = ctx.accounts.owner.key();
@@ -42,21 +102,53 @@ pub mod degen_nft {
         card_data.win_rate = win_rate;
         card_data.last_updated = Clock::get()?.unix_timestamp;
         card_data.mint_number = ctx.accounts.global_state.total_minted + 1;
-        card_data.is_genesis = ctx.accounts.global_state.total_minted < 1000; // First 1000 are Genesis
+        card_data.is_genesis = ctx.accounts.global_state.total_minted < GENESIS_SUPPLY;
+        card_data.staking_tier = staking_tier;
+        card_data.mode = mode;
+
+        // Seed the history with the minted stats
+        let score_history = &mut ctx.accounts.score_history;
+        score_history.card_data = card_data.key();
+        score_history.push(ScoreSnapshot::from_card(card_data));
 
         // Update global state
         let global_state = &mut ctx.accounts.global_state;
         global_state.total_minted += 1;
 
-        // Mint 1 token to owner (NFT standard)
-        let cpi_accounts = token::MintTo {
+        let signer_seeds: &[&[&[u8]]] = &[&[b"card_authority", &[ctx.bumps.card_authority]]];
+        let nft = CardNft {
             mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.token_account.to_account_info(),
-            authority: ctx.accounts.owner.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            holder: ctx.accounts.owner.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            card_authority: ctx.accounts.card_authority.to_account_info(),
+            collection: card_collection(
+                global_state,
+                ctx.accounts.collection_mint.as_deref(),
+                ctx.accounts.collection_metadata.as_ref(),
+                ctx.accounts.collection_master_edition.as_ref(),
+            )?,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::mint_to(cpi_ctx, 1)?;
+        issue_card_nft(&nft, card_data, global_state, uri, signer_seeds)?;
+
+        emit!(CardMinted {
+            owner: card_data.owner,
+            mint: card_data.mint,
+            mint_number: card_data.mint_number,
+            degen_score,
+            total_trades,
+            total_volume,
+            win_rate,
+            is_genesis: card_data.is_genesis,
+            staking_tier: card_data.staking_tier,
+            mode,
+            price_paid: price,
+            timestamp: card_data.last_updated,
+        });
 
         msg!(
             "Minted DegenScore NFT #{} - Score: {}, Trades: {}, Win Rate: {}%",
@@ -69,29 +161,116 @@ pub mod degen_nft {
         Ok(())
     }
 
-    /// Update the score on an existing NFT (owner only)
+    /// Update the score on an existing NFT (current holder only), at most once
+    /// per update interval of the holder's staking tier, if one is set
+    ///
+    /// The new stats must be attested by the score oracle: the transaction has to
+    /// carry an ed25519 signature instruction, immediately before this one, where
+    /// the oracle signs the borsh-serialized `ScoreAttestation`. The attested URI
+    /// replaces the card's metadata URI so marketplaces pick up the new art.
     pub fn update_score(
         ctx: Context<UpdateScore>,
         new_degen_score: u8,
         new_total_trades: u32,
         new_total_volume: u64,
         new_win_rate: u8,
+        attested_at: i64,
+        new_uri: String,
     ) -> Result<()> {
         require!(new_degen_score <= 100, ErrorCode::InvalidScore);
         require!(new_win_rate <= 100, ErrorCode::InvalidWinRate);
+        require!(new_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
 
         let card_data = &mut ctx.accounts.card_data;
+        let clock = Clock::get()?;
+
+        let staking_tier = staking_interface::tier_of(ctx.accounts.staker_summary.as_deref());
+        require!(
+            clock.unix_timestamp - card_data.last_updated >= ctx.accounts.global_state.update_interval_for(staking_tier),
+            ErrorCode::UpdateTooSoon
+        );
+
+        check_attestation_time(attested_at, card_data.last_attested_at, clock.unix_timestamp)?;
+
+        let attestation = ScoreAttestation {
+            mint: card_data.mint,
+            degen_score: new_degen_score,
+            total_trades: new_total_trades,
+            total_volume: new_total_volume,
+            win_rate: new_win_rate,
+            timestamp: attested_at,
+            uri: new_uri.clone(),
+        };
+        verify_oracle_signature(
+            &ctx.accounts.instructions,
+            &ctx.accounts.global_state.score_oracle,
+            &attestation.try_to_vec()?,
+        )?;
 
         // Store old values for event
         let old_score = card_data.degen_score;
+        let old_total_trades = card_data.total_trades;
+        let old_total_volume = card_data.total_volume;
+        let old_win_rate = card_data.win_rate;
 
         // Update values
         card_data.degen_score = new_degen_score;
         card_data.total_trades = new_total_trades;
         card_data.total_volume = new_total_volume;
         card_data.win_rate = new_win_rate;
-        card_data.last_updated = Clock::get()?.unix_timestamp;
+        card_data.last_updated = clock.unix_timestamp;
+        card_data.last_attested_at = attested_at;
         card_data.update_count += 1;
+        card_data.staking_tier = staking_tier;
+        card_data.owner = ctx.accounts.owner.key();
+
+        ctx.accounts.score_history.push(ScoreSnapshot::from_card(card_data));
+
+        // Rotate the metadata URI, keeping everything else as minted
+        let metadata = Metadata::from_account_info(&ctx.accounts.metadata)?;
+        let update_metadata_ix = update_metadata_accounts_v2(
+            mpl_token_metadata::ID,
+            ctx.accounts.metadata.key(),
+            ctx.accounts.card_authority.key(),
+            None,
+            Some(DataV2 {
+                name: metadata.data.name.trim_end_matches('\0').to_string(),
+                symbol: metadata.data.symbol.trim_end_matches('\0').to_string(),
+                uri: new_uri.clone(),
+                seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+                creators: metadata.data.creators,
+                collection: metadata.collection,
+                uses: metadata.uses,
+            }),
+            None,
+            None,
+        );
+        let signer_seeds: &[&[&[u8]]] = &[&[b"card_authority", &[ctx.bumps.card_authority]]];
+        invoke_signed(
+            &update_metadata_ix,
+            &[
+                ctx.accounts.metadata.to_account_info(),
+                ctx.accounts.card_authority.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        emit!(ScoreUpdated {
+            mint: card_data.mint,
+            owner: card_data.owner,
+            old_degen_score: old_score,
+            new_degen_score,
+            old_total_trades,
+            new_total_trades,
+            old_total_volume,
+            new_total_volume,
+            old_win_rate,
+            new_win_rate,
+            update_count: card_data.update_count,
+            staking_tier,
+            attested_at,
+            uri: new_uri,
+        });
 
         msg!(
             "Updated DegenScore NFT #{} - Score: {} -> {}, Trades: {}, Updates: {}",
@@ -105,6 +284,127 @@ pub mod degen_nft {
         Ok(())
     }
 
+    /// Point `CardData.owner` at the wallet currently holding the card, e.g.
+    /// after a marketplace sale (anyone)
+    pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
+        let card_data = &mut ctx.accounts.card_data;
+        let old_owner = card_data.owner;
+        card_data.owner = ctx.accounts.token_account.owner;
+
+        emit!(OwnerSynced {
+            mint: card_data.mint,
+            old_owner,
+            new_owner: card_data.owner,
+        });
+
+        msg!(
+            "DegenScore NFT #{} owner synced: {} -> {}",
+            card_data.mint_number,
+            old_owner,
+            card_data.owner
+        );
+
+        Ok(())
+    }
+
+    /// Move a soulbound card to another wallet: burns the frozen NFT and mints a
+    /// new one to `new_owner` carrying over the card number, stats and history.
+    /// Both wallets sign. Tradeable cards are simply transferred instead.
+    pub fn burn_and_remint(ctx: Context<BurnAndRemint>) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[b"card_authority", &[ctx.bumps.card_authority]]];
+        let old_card_data = &ctx.accounts.old_card_data;
+
+        // The new card keeps the art the old one had
+        let old_metadata = Metadata::from_account_info(&ctx.accounts.old_metadata)?;
+        let uri = old_metadata.data.uri.trim_end_matches('\0').to_string();
+
+        let collection = card_collection(
+            &ctx.accounts.global_state,
+            ctx.accounts.collection_mint.as_deref(),
+            ctx.accounts.collection_metadata.as_ref(),
+            ctx.accounts.collection_master_edition.as_ref(),
+        )?;
+
+        // Burning a verified member needs the collection's metadata
+        let old_collection_metadata = collection
+            .as_ref()
+            .filter(|collection| is_verified_card(&old_metadata, &collection.mint.key()))
+            .map(|collection| collection.metadata.clone());
+
+        // Thaw with the delegate approved at mint, then burn the token and close
+        // its metadata and edition
+        thaw_card(
+            &ctx.accounts.card_authority,
+            &ctx.accounts.old_token_account.to_account_info(),
+            &ctx.accounts.old_master_edition,
+            &ctx.accounts.old_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        let burn_ix = burn_nft(
+            mpl_token_metadata::ID,
+            ctx.accounts.old_metadata.key(),
+            ctx.accounts.owner.key(),
+            ctx.accounts.old_mint.key(),
+            ctx.accounts.old_token_account.key(),
+            ctx.accounts.old_master_edition.key(),
+            ctx.accounts.token_program.key(),
+            old_collection_metadata.as_ref().map(|metadata| metadata.key()),
+        );
+        let mut burn_accounts = vec![
+            ctx.accounts.old_metadata.to_account_info(),
+            ctx.accounts.owner.to_account_info(),
+            ctx.accounts.old_mint.to_account_info(),
+            ctx.accounts.old_token_account.to_account_info(),
+            ctx.accounts.old_master_edition.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+        burn_accounts.extend(old_collection_metadata);
+        invoke(&burn_ix, &burn_accounts)?;
+
+        let card_data = &mut ctx.accounts.card_data;
+        card_data.carry_over(old_card_data);
+        card_data.owner = ctx.accounts.new_owner.key();
+        card_data.mint = ctx.accounts.mint.key();
+
+        let score_history = &mut ctx.accounts.score_history;
+        score_history.card_data = card_data.key();
+        score_history.head = ctx.accounts.old_score_history.head;
+        score_history.count = ctx.accounts.old_score_history.count;
+        score_history.snapshots = ctx.accounts.old_score_history.snapshots;
+
+        let nft = CardNft {
+            mint: ctx.accounts.mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            holder: ctx.accounts.new_owner.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            card_authority: ctx.accounts.card_authority.to_account_info(),
+            collection,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        issue_card_nft(&nft, card_data, &ctx.accounts.global_state, uri, signer_seeds)?;
+
+        emit!(CardReminted {
+            old_owner: old_card_data.owner,
+            new_owner: card_data.owner,
+            old_mint: old_card_data.mint,
+            new_mint: card_data.mint,
+            mint_number: card_data.mint_number,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Reminted DegenScore NFT #{} to {}",
+            card_data.mint_number,
+            card_data.owner
+        );
+
+        Ok(())
+    }
+
     /// Initialize the global state (one-time)
     pub fn initialize_global_state(ctx: Context<InitializeGlobalState>) -> Result<()> {
         let global_state = &mut ctx.accounts.global_state;
@@ -112,25 +412,654 @@ pub mod degen_nft {
         global_state.total_minted = 0;
         global_state.treasury = ctx.accounts.treasury.key();
         global_state.royalty_basis_points = 500; // 5% royalties
+        global_state.score_oracle = ctx.accounts.score_oracle.key();
+        global_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        global_state.mint_price = 0;
+        global_state.tier_discounts_bp = DEFAULT_TIER_DISCOUNTS_BP;
+        global_state.collection_mint = Pubkey::default();
+        global_state.update_intervals = DEFAULT_UPDATE_INTERVALS;
+
+        emit!(GlobalStateInitialized {
+            authority: global_state.authority,
+            treasury: global_state.treasury,
+            score_oracle: global_state.score_oracle,
+            royalty_basis_points: global_state.royalty_basis_points,
+        });
 
         msg!("Global state initialized - Royalties: 5%");
 
         Ok(())
     }
 
-    /// Update royalty percentage (authority only)
-    pub fn update_royalties(ctx: Context<UpdateRoyalties>, new_royalty_basis_points: u16) -> Result<()> {
+    /// Create the collection NFT every card is verified into (authority, once).
+    /// Cards minted before it exists are left outside the collection. The card
+    /// authority PDA holds the collection token and its update authority.
+    pub fn create_collection(ctx: Context<CreateCollection>, uri: String) -> Result<()> {
+        require!(uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"card_authority", &[ctx.bumps.card_authority]]];
+
+        let cpi_accounts = token::MintTo {
+            mint: ctx.accounts.collection_mint.to_account_info(),
+            to: ctx.accounts.collection_token_account.to_account_info(),
+            authority: ctx.accounts.card_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::mint_to(cpi_ctx, 1)?;
+
+        let global_state = &mut ctx.accounts.global_state;
+        let create_metadata_ix = create_metadata_accounts_v3(
+            mpl_token_metadata::ID,
+            ctx.accounts.collection_metadata.key(),
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.card_authority.key(),
+            ctx.accounts.authority.key(),
+            ctx.accounts.card_authority.key(),
+            COLLECTION_NAME.to_string(),
+            CARD_SYMBOL.to_string(),
+            uri,
+            Some(vec![Creator {
+                address: global_state.treasury,
+                verified: false,
+                share: 100,
+            }]),
+            global_state.royalty_basis_points,
+            true,
+            true,
+            None,
+            None,
+            None,
+        );
+        invoke_signed(
+            &create_metadata_ix,
+            &[
+                ctx.accounts.collection_metadata.to_account_info(),
+                ctx.accounts.collection_mint.to_account_info(),
+                ctx.accounts.card_authority.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        let create_master_edition_ix = create_master_edition_v3(
+            mpl_token_metadata::ID,
+            ctx.accounts.collection_master_edition.key(),
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.card_authority.key(),
+            ctx.accounts.card_authority.key(),
+            ctx.accounts.collection_metadata.key(),
+            ctx.accounts.authority.key(),
+            Some(0),
+        );
+        invoke_signed(
+            &create_master_edition_ix,
+            &[
+                ctx.accounts.collection_master_edition.to_account_info(),
+                ctx.accounts.collection_mint.to_account_info(),
+                ctx.accounts.card_authority.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.collection_metadata.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        global_state.collection_mint = ctx.accounts.collection_mint.key();
+
+        emit!(CollectionCreated {
+            collection_mint: global_state.collection_mint,
+        });
+
+        msg!("DegenScore collection created: {}", global_state.collection_mint);
+
+        Ok(())
+    }
+
+    /// Rotate the score oracle signing key (authority only)
+    pub fn update_score_oracle(ctx: Context<UpdateScoreOracle>, new_score_oracle: Pubkey) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        let old_score_oracle = global_state.score_oracle;
+        global_state.score_oracle = new_score_oracle;
+
+        emit!(ScoreOracleUpdated {
+            old_score_oracle,
+            new_score_oracle,
+        });
+
+        msg!("Score oracle updated to: {}", new_score_oracle);
+
+        Ok(())
+    }
+
+    /// Set the $DEGEN mint price and its discounts by staking tier (authority only)
+    pub fn set_mint_price(ctx: Context<SetMintPrice>, mint_price: u64, tier_discounts_bp: [u16; 3]) -> Result<()> {
+        require!(
+            tier_discounts_bp.iter().all(|discount| *discount <= 10000),
+            ErrorCode::InvalidDiscount
+        );
+
+        let global_state = &mut ctx.accounts.global_state;
+        let old_mint_price = global_state.mint_price;
+        global_state.mint_price = mint_price;
+        global_state.tier_discounts_bp = tier_discounts_bp;
+
+        emit!(MintPriceUpdated {
+            old_mint_price,
+            new_mint_price: mint_price,
+            tier_discounts_bp,
+        });
+
+        msg!("Mint price set to {} $DEGEN base units", mint_price);
+
+        Ok(())
+    }
+
+    /// Set the minimum time between score updates of a card, by the holder's
+    /// staking tier (authority only). Stakers never wait longer than non-stakers;
+    /// all zeros removes the limit.
+    pub fn set_update_intervals(ctx: Context<SetUpdateIntervals>, update_intervals: [i64; 3]) -> Result<()> {
+        require!(
+            0 <= update_intervals[2]
+                && update_intervals[2] <= update_intervals[1]
+                && update_intervals[1] <= update_intervals[0],
+            ErrorCode::InvalidUpdateInterval
+        );
+
+        let global_state = &mut ctx.accounts.global_state;
+        let old_update_intervals = global_state.update_intervals;
+        global_state.update_intervals = update_intervals;
+
+        emit!(UpdateIntervalsUpdated {
+            old_update_intervals,
+            new_update_intervals: update_intervals,
+        });
+
+        msg!("Score update intervals set to {:?} seconds", update_intervals);
+
+        Ok(())
+    }
+
+    /// Schedule a royalty change, executable once the timelock delay has passed (authority only)
+    pub fn queue_royalty_update(ctx: Context<QueueRoyaltyUpdate>, new_royalty_basis_points: u16) -> Result<()> {
         require!(new_royalty_basis_points <= 1000, ErrorCode::RoyaltiesTooHigh); // Max 10%
 
+        let now = Clock::get()?.unix_timestamp;
+        let eta = now + ctx.accounts.global_state.timelock_delay_at(now);
+        let pending_royalty_update = &mut ctx.accounts.pending_royalty_update;
+        pending_royalty_update.royalty_basis_points = new_royalty_basis_points;
+        pending_royalty_update.eta = eta;
+        pending_royalty_update.queued_by = ctx.accounts.authority.key();
+
+        emit!(RoyaltyUpdateQueued {
+            royalty_basis_points: new_royalty_basis_points,
+            eta,
+        });
+
+        msg!(
+            "Royalty update queued - {}%, executable at {}",
+            new_royalty_basis_points / 100,
+            eta
+        );
+
+        Ok(())
+    }
+
+    /// Drop a queued royalty change before it is executed (authority only)
+    pub fn cancel_royalty_update(ctx: Context<CancelRoyaltyUpdate>) -> Result<()> {
+        let pending_royalty_update = &ctx.accounts.pending_royalty_update;
+
+        emit!(RoyaltyUpdateCancelled {
+            royalty_basis_points: pending_royalty_update.royalty_basis_points,
+            eta: pending_royalty_update.eta,
+        });
+
+        msg!("Royalty update cancelled");
+
+        Ok(())
+    }
+
+    /// Apply a queued royalty change once its timelock has passed (anyone)
+    pub fn execute_royalty_update(ctx: Context<ExecuteRoyaltyUpdate>) -> Result<()> {
+        let pending_royalty_update = &ctx.accounts.pending_royalty_update;
+        require!(
+            Clock::get()?.unix_timestamp >= pending_royalty_update.eta,
+            ErrorCode::TimelockNotExpired
+        );
+
+        let global_state = &mut ctx.accounts.global_state;
+        let old_royalty_basis_points = global_state.royalty_basis_points;
+        global_state.royalty_basis_points = pending_royalty_update.royalty_basis_points;
+
+        emit!(RoyaltiesUpdated {
+            old_royalty_basis_points,
+            new_royalty_basis_points: global_state.royalty_basis_points,
+        });
+
+        msg!("Royalties updated to: {}%", global_state.royalty_basis_points / 100);
+
+        Ok(())
+    }
+
+    /// Change the delay applied to newly queued changes (authority only). A
+    /// shorter delay only applies once the current one has passed.
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+        require!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            ErrorCode::InvalidTimelockDelay
+        );
+
+        let now = Clock::get()?.unix_timestamp;
         let global_state = &mut ctx.accounts.global_state;
-        global_state.royalty_basis_points = new_royalty_basis_points;
+        let old_timelock_delay = global_state.timelock_delay_at(now);
+        let effective_at = global_state.set_timelock_delay(timelock_delay, now);
 
-        msg!("Royalties updated to: {}%", new_royalty_basis_points / 100);
+        emit!(TimelockDelayUpdated {
+            old_timelock_delay,
+            new_timelock_delay: timelock_delay,
+            effective_at,
+        });
+
+        msg!("Timelock delay set to {} seconds from {}", timelock_delay, effective_at);
+
+        Ok(())
+    }
+
+    /// Propose a new authority; it takes over once it calls `accept_authority`.
+    /// Proposing `Pubkey::default()` cancels a pending proposal.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: global_state.authority,
+            pending_authority: new_authority,
+        });
+
+        msg!("Authority transfer proposed to: {}", new_authority);
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (proposed authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        let old_authority = global_state.authority;
+        global_state.authority = global_state.pending_authority;
+        global_state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: global_state.authority,
+        });
+
+        msg!("Authority transferred to: {}", global_state.authority);
+
+        Ok(())
+    }
+
+    /// Give up admin control for good (authority only)
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        let old_authority = global_state.authority;
+        global_state.authority = Pubkey::default();
+        global_state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: Pubkey::default(),
+        });
+
+        msg!("Authority renounced");
 
         Ok(())
     }
 }
 
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Charge the card owner `price` $DEGEN through `transfer_with_fees`, paid to
+/// the treasury's token account
+fn pay_mint_price(accounts: &MintDegenCard, price: u64) -> Result<()> {
+    let missing = || error!(ErrorCode::MissingPaymentAccounts);
+    let token_data = accounts.degen_token_data.as_ref().ok_or_else(missing)?;
+    let degen_mint = accounts.degen_mint.as_ref().ok_or_else(missing)?;
+    let payer_token_account = accounts.payer_token_account.as_ref().ok_or_else(missing)?;
+    let treasury_token_account = accounts.treasury_token_account.as_ref().ok_or_else(missing)?;
+    let fee_treasury_token_account = accounts.fee_treasury_token_account.as_ref().ok_or_else(missing)?;
+    let degen_token_program = accounts.degen_token_program.as_ref().ok_or_else(missing)?;
+
+    // The price goes to the card treasury, the token's fee share to the token treasury
+    require_keys_eq!(degen_mint.key(), token_data.mint, ErrorCode::InvalidPaymentAccount);
+    require_keys_eq!(treasury_token_account.owner, accounts.global_state.treasury, ErrorCode::InvalidPaymentAccount);
+    require_keys_eq!(treasury_token_account.mint, token_data.mint, ErrorCode::InvalidPaymentAccount);
+    require_keys_eq!(fee_treasury_token_account.owner, token_data.treasury, ErrorCode::InvalidPaymentAccount);
+    require_keys_eq!(fee_treasury_token_account.mint, token_data.mint, ErrorCode::InvalidPaymentAccount);
+    if let Some(exemption) = accounts.treasury_exemption.as_ref() {
+        require_keys_eq!(exemption.token_account, treasury_token_account.key(), ErrorCode::InvalidPaymentAccount);
+    }
+
+    let cpi_accounts = degen_token::cpi::accounts::TransferWithFees {
+        token_data: token_data.to_account_info(),
+        from: payer_token_account.to_account_info(),
+        to: treasury_token_account.to_account_info(),
+        treasury_account: fee_treasury_token_account.to_account_info(),
+        mint: degen_mint.to_account_info(),
+        from_authority: accounts.owner.to_account_info(),
+        exemption: accounts.treasury_exemption.as_ref().map(|exemption| exemption.to_account_info()),
+        token_program: accounts.token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(degen_token_program.to_account_info(), cpi_accounts);
+    degen_token::cpi::transfer_with_fees(cpi_ctx, price)
+}
+
+/// Accounts used to mint a card's NFT to `holder`
+struct CardNft<'info> {
+    mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    holder: AccountInfo<'info>, // Signs, as it approves the lock of soulbound cards
+    payer: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    card_authority: AccountInfo<'info>,
+    collection: Option<CardCollection<'info>>, // None until the collection is created
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
+
+/// The DegenScore collection's mint, metadata and master edition
+struct CardCollection<'info> {
+    mint: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+}
+
+/// The collection accounts a card is verified into: none before
+/// `create_collection`, all three afterwards
+fn card_collection<'info>(
+    global_state: &GlobalState,
+    mint: Option<&Account<'info, Mint>>,
+    metadata: Option<&AccountInfo<'info>>,
+    master_edition: Option<&AccountInfo<'info>>,
+) -> Result<Option<CardCollection<'info>>> {
+    if global_state.collection_mint == Pubkey::default() {
+        return Ok(None);
+    }
+    match (mint, metadata, master_edition) {
+        (Some(mint), Some(metadata), Some(master_edition)) => Ok(Some(CardCollection {
+            mint: mint.to_account_info(),
+            metadata: metadata.clone(),
+            master_edition: master_edition.clone(),
+        })),
+        _ => err!(ErrorCode::InvalidCollection),
+    }
+}
+
+/// Mint the single token of `card_data`'s NFT with its Metaplex metadata and
+/// master edition, verify it into the collection if there is one, then freeze
+/// it in the holder's wallet if it is soulbound
+fn issue_card_nft(
+    nft: &CardNft,
+    card_data: &CardData,
+    global_state: &GlobalState,
+    uri: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Mint 1 token to the holder (NFT standard)
+    let cpi_accounts = token::MintTo {
+        mint: nft.mint.clone(),
+        to: nft.token_account.clone(),
+        authority: nft.card_authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(nft.token_program.clone(), cpi_accounts, signer_seeds);
+    token::mint_to(cpi_ctx, 1)?;
+
+    // Metaplex metadata: royalties from global state, paid to the treasury
+    let create_metadata_ix = create_metadata_accounts_v3(
+        mpl_token_metadata::ID,
+        nft.metadata.key(),
+        nft.mint.key(),
+        nft.card_authority.key(),
+        nft.payer.key(),
+        nft.card_authority.key(),
+        format!("DegenScore #{}", card_data.mint_number),
+        CARD_SYMBOL.to_string(),
+        uri,
+        Some(vec![Creator {
+            address: global_state.treasury,
+            verified: false,
+            share: 100,
+        }]),
+        global_state.royalty_basis_points,
+        true,
+        true, // Mutable so the URI can follow score updates
+        nft.collection.as_ref().map(|collection| Collection {
+            verified: false,
+            key: collection.mint.key(),
+        }),
+        None,
+        None,
+    );
+    invoke_signed(
+        &create_metadata_ix,
+        &[
+            nft.metadata.clone(),
+            nft.mint.clone(),
+            nft.card_authority.clone(),
+            nft.payer.clone(),
+            nft.system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    // Master edition with max supply 0: takes over the mint and freeze
+    // authorities so no second token can ever be minted
+    let create_master_edition_ix = create_master_edition_v3(
+        mpl_token_metadata::ID,
+        nft.master_edition.key(),
+        nft.mint.key(),
+        nft.card_authority.key(),
+        nft.card_authority.key(),
+        nft.metadata.key(),
+        nft.payer.key(),
+        Some(0),
+    );
+    invoke_signed(
+        &create_master_edition_ix,
+        &[
+            nft.master_edition.clone(),
+            nft.mint.clone(),
+            nft.card_authority.clone(),
+            nft.payer.clone(),
+            nft.metadata.clone(),
+            nft.token_program.clone(),
+            nft.system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    // The card authority is the collection's update authority
+    if let Some(collection) = &nft.collection {
+        let verify_collection_ix = verify_collection(
+            mpl_token_metadata::ID,
+            nft.metadata.key(),
+            nft.card_authority.key(),
+            nft.payer.key(),
+            collection.mint.key(),
+            collection.metadata.key(),
+            collection.master_edition.key(),
+            None,
+        );
+        invoke_signed(
+            &verify_collection_ix,
+            &[
+                nft.metadata.clone(),
+                nft.card_authority.clone(),
+                nft.payer.clone(),
+                collection.mint.clone(),
+                collection.metadata.clone(),
+                collection.master_edition.clone(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    if card_data.mode == CardMode::Soulbound {
+        // The edition now holds the freeze authority: the card authority freezes
+        // through Metaplex as the token's delegate. A frozen account can neither
+        // transfer nor revoke the delegate.
+        let cpi_accounts = token::Approve {
+            to: nft.token_account.clone(),
+            delegate: nft.card_authority.clone(),
+            authority: nft.holder.clone(),
+        };
+        token::approve(CpiContext::new(nft.token_program.clone(), cpi_accounts), 1)?;
+
+        let freeze_ix = freeze_delegated_account(
+            mpl_token_metadata::ID,
+            nft.card_authority.key(),
+            nft.token_account.key(),
+            nft.master_edition.key(),
+            nft.mint.key(),
+        );
+        invoke_signed(
+            &freeze_ix,
+            &[
+                nft.card_authority.clone(),
+                nft.token_account.clone(),
+                nft.master_edition.clone(),
+                nft.mint.clone(),
+                nft.token_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Thaw a soulbound card's token account, as the delegate approved at mint
+fn thaw_card<'info>(
+    card_authority: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    master_edition: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let thaw_ix = thaw_delegated_account(
+        mpl_token_metadata::ID,
+        card_authority.key(),
+        token_account.key(),
+        master_edition.key(),
+        mint.key(),
+    );
+    invoke_signed(
+        &thaw_ix,
+        &[
+            card_authority.clone(),
+            token_account.clone(),
+            master_edition.clone(),
+            mint.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Whether `metadata` carries a verified membership of the collection
+/// `collection_mint`
+pub fn is_verified_card(metadata: &Metadata, collection_mint: &Pubkey) -> bool {
+    metadata
+        .collection
+        .as_ref()
+        .is_some_and(|collection| collection.verified && collection.key == *collection_mint)
+}
+
+/// Whether `mint` belongs to the collection `collection_mint` (see
+/// `GlobalState::collection_mint`), given the account passed as its Metaplex
+/// metadata. Accounts that aren't the mint's metadata PDA count as "no".
+pub fn is_collection_member(metadata_info: &AccountInfo, mint: &Pubkey, collection_mint: &Pubkey) -> Result<bool> {
+    if metadata_info.key() != find_metadata_account(mint).0
+        || *metadata_info.owner != mpl_token_metadata::ID
+        || metadata_info.data_is_empty()
+    {
+        return Ok(false);
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    Ok(metadata.mint == *mint && is_verified_card(&metadata, collection_mint))
+}
+
+/// Whether `token_account` holds the single token of the card `mint`
+pub fn holds_card(token_account: &TokenAccount, mint: &Pubkey) -> bool {
+    token_account.mint == *mint && token_account.amount == 1
+}
+
+/// Reject replayed, stale or future-dated attestations.
+pub fn check_attestation_time(attested_at: i64, last_attested_at: i64, now: i64) -> Result<()> {
+    require!(attested_at > last_attested_at, ErrorCode::StaleAttestation);
+    require!(now - attested_at <= MAX_ATTESTATION_AGE, ErrorCode::StaleAttestation);
+    require!(attested_at - now <= MAX_ATTESTATION_SKEW, ErrorCode::AttestationFromFuture);
+    Ok(())
+}
+
+/// Ensure the instruction preceding the current one is an ed25519 signature
+/// check by `oracle` over exactly `message`.
+fn verify_oracle_signature(instructions: &AccountInfo, oracle: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, ErrorCode::MissingOracleSignature);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    verify_ed25519_instruction(&ed25519_ix, oracle, message)
+}
+
+/// Check that `ix` is an ed25519 program instruction verifying a single
+/// signature by `signer` over `message`.
+///
+/// The ed25519 program has already checked the signature itself when the
+/// transaction executes; this only makes sure it covered the expected key and
+/// data, all contained in the instruction itself.
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::MissingOracleSignature);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidOracleSignature);
+
+    // Layout: num_signatures (u8), padding (u8), then 7 u16 offsets:
+    // signature, signature ix, public key, public key ix, message, message size, message ix
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidOracleSignature);
+
+    let read_u16 = |index: usize| u16::from_le_bytes([data[2 + index * 2], data[3 + index * 2]]);
+    let signature_ix_index = read_u16(1);
+    let public_key_offset = read_u16(2) as usize;
+    let public_key_ix_index = read_u16(3);
+    let message_offset = read_u16(4) as usize;
+    let message_size = read_u16(5) as usize;
+    let message_ix_index = read_u16(6);
+
+    // u16::MAX means "this instruction", anything else could point at unrelated data
+    require!(
+        signature_ix_index == u16::MAX && public_key_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidOracleSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidOracleSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidOracleSignature)?;
+
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidOracleSignature);
+    require!(signed_message == message, ErrorCode::InvalidOracleSignature);
+
+    Ok(())
+}
+
 // ============================================================================
 // CONTEXTS
 // ============================================================================
@@ -146,6 +1075,15 @@ pub struct MintDegenCard<'info> {
     )]
     pub card_data: Account<'info, CardData>,
 
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ScoreHistory::INIT_SPACE,
+        seeds = [b"score_history", card_data.key().as_ref()],
+        bump
+    )]
+    pub score_history: Box<Account<'info, ScoreHistory>>,
+
     #[account(
         mut,
         seeds = [b"global_state"],
@@ -156,11 +1094,22 @@ pub struct MintDegenCard<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
 
+    /// Owner's staking summary for tier perks; omitted if they never staked
+    #[account(
+        seeds = [STAKER_SUMMARY_SEED, owner.key().as_ref()],
+        bump,
+        seeds::program = staking_interface::ID
+    )]
+    pub staker_summary: Option<Account<'info, StakerSummary>>,
+
+    /// Card mint. The master edition takes over both its mint and freeze
+    /// authorities, so the freeze authority has to be set here.
     #[account(
         init,
         payer = owner,
         mint::decimals = 0,
-        mint::authority = owner,
+        mint::authority = card_authority,
+        mint::freeze_authority = card_authority,
     )]
     pub mint: Account<'info, Mint>,
 
@@ -172,53 +1121,572 @@ pub struct MintDegenCard<'info> {
     )]
     pub token_account: Account<'info, TokenAccount>,
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// CHECK: PDA holding mint and update authority over every card; writable
+    /// as Metaplex's verify_collection takes the collection authority as such
+    #[account(
+        mut,
+        seeds = [b"card_authority"],
+        bump
+    )]
+    pub card_authority: AccountInfo<'info>,
 
-#[derive(Accounts)]
-pub struct UpdateScore<'info> {
+    /// CHECK: Metaplex metadata PDA, created by the token metadata program
     #[account(
         mut,
-        seeds = [b"card_data", card_data.mint.as_ref()],
-        bump,
-        has_one = owner
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), mint.key().as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
     )]
-    pub card_data: Account<'info, CardData>,
+    pub metadata: AccountInfo<'info>,
 
-    pub owner: Signer<'info>,
-}
+    /// CHECK: Metaplex master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), mint.key().as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub master_edition: AccountInfo<'info>,
 
-#[derive(Accounts)]
-pub struct InitializeGlobalState<'info> {
+    /// The collection accounts are required once `create_collection` has run
+    #[account(address = global_state.collection_mint @ ErrorCode::InvalidCollection)]
+    pub collection_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: Metaplex metadata PDA of the collection
     #[account(
-        init,
-        payer = authority,
-        space = 8 + GlobalState::INIT_SPACE,
-        seeds = [b"global_state"],
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), global_state.collection_mint.as_ref()],
+        seeds::program = mpl_token_metadata::ID,
         bump
     )]
-    pub global_state: Account<'info, GlobalState>,
+    pub collection_metadata: Option<AccountInfo<'info>>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// CHECK: Metaplex master edition PDA of the collection
+    #[account(
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), global_state.collection_mint.as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub collection_master_edition: Option<AccountInfo<'info>>,
 
-    /// CHECK: Treasury wallet address
-    pub treasury: AccountInfo<'info>,
+    /// CHECK: Metaplex token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
-}
 
-#[derive(Accounts)]
-pub struct UpdateRoyalties<'info> {
+    // Mint payment, only needed when the owner owes a price
+
     #[account(
-        mut,
-        seeds = [b"global_state"],
+        seeds = [b"token_data"],
         bump,
-        has_one = authority
+        seeds::program = degen_token::ID
+    )]
+    pub degen_token_data: Option<Box<Account<'info, TokenData>>>,
+
+    #[account(mut)]
+    pub degen_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Owner's $DEGEN, debited with the owner's signature
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Receives the price; held by `global_state.treasury`
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Receives the token's treasury fee; held by `token_data.treasury`
+    #[account(mut)]
+    pub fee_treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Exemption registry entry of `treasury_token_account`, if it has one
+    pub treasury_exemption: Option<Box<Account<'info, Exemption>>>,
+
+    pub degen_token_program: Option<Program<'info, DegenToken>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"card_data", card_data.mint.as_ref()],
+        bump
+    )]
+    pub card_data: Account<'info, CardData>,
+
+    #[account(
+        mut,
+        seeds = [b"score_history", card_data.key().as_ref()],
+        bump,
+        has_one = card_data
+    )]
+    pub score_history: Box<Account<'info, ScoreHistory>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub owner: Signer<'info>,
+
+    /// Owner's token account for the card; ownership follows the token, not
+    /// the possibly stale `card_data.owner`
+    #[account(
+        constraint = token_account.owner == owner.key() @ ErrorCode::NotCardHolder,
+        constraint = holds_card(&token_account, &card_data.mint) @ ErrorCode::NotCardHolder
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Owner's staking summary for tier perks; omitted if they never staked
+    #[account(
+        seeds = [STAKER_SUMMARY_SEED, owner.key().as_ref()],
+        bump,
+        seeds::program = staking_interface::ID
+    )]
+    pub staker_summary: Option<Account<'info, StakerSummary>>,
+
+    /// CHECK: Instructions sysvar, used to read the oracle's ed25519 signature
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// CHECK: PDA holding update authority over every card's metadata
+    #[account(
+        seeds = [b"card_authority"],
+        bump
+    )]
+    pub card_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex metadata PDA of the card's mint
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), card_data.mint.as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"card_data", card_data.mint.as_ref()],
+        bump
+    )]
+    pub card_data: Account<'info, CardData>,
+
+    /// Token account currently holding the card
+    #[account(constraint = holds_card(&token_account, &card_data.mint) @ ErrorCode::NotCardHolder)]
+    pub token_account: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct BurnAndRemint<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"card_data", old_mint.key().as_ref()],
+        bump,
+        has_one = owner,
+        constraint = old_card_data.mode == CardMode::Soulbound @ ErrorCode::NotSoulbound
+    )]
+    pub old_card_data: Box<Account<'info, CardData>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"score_history", old_card_data.key().as_ref()],
+        bump
+    )]
+    pub old_score_history: Box<Account<'info, ScoreHistory>>,
+
+    #[account(mut)]
+    pub old_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = old_mint,
+        associated_token::authority = owner,
+    )]
+    pub old_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metaplex metadata PDA of the old card, closed by the burn
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), old_mint.key().as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub old_metadata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex master edition PDA of the old card, closed by the burn
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), old_mint.key().as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub old_master_edition: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CardData::INIT_SPACE,
+        seeds = [b"card_data", mint.key().as_ref()],
+        bump
+    )]
+    pub card_data: Box<Account<'info, CardData>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ScoreHistory::INIT_SPACE,
+        seeds = [b"score_history", card_data.key().as_ref()],
+        bump
+    )]
+    pub score_history: Box<Account<'info, ScoreHistory>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Current holder; pays for the new card and gets the old card's rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Wallet the card moves to; signs to approve the lock on its new card
+    pub new_owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = card_authority,
+        mint::freeze_authority = card_authority,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = new_owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA holding mint and update authority over every card; writable
+    /// as Metaplex's verify_collection takes the collection authority as such
+    #[account(
+        mut,
+        seeds = [b"card_authority"],
+        bump
+    )]
+    pub card_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), mint.key().as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), mint.key().as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub master_edition: AccountInfo<'info>,
+
+    /// The collection accounts are required once `create_collection` has run
+    #[account(address = global_state.collection_mint @ ErrorCode::InvalidCollection)]
+    pub collection_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: Metaplex metadata PDA of the collection, checked by the burn of a
+    /// verified card
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), global_state.collection_mint.as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub collection_metadata: Option<AccountInfo<'info>>,
+
+    /// CHECK: Metaplex master edition PDA of the collection
+    #[account(
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), global_state.collection_mint.as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub collection_master_edition: Option<AccountInfo<'info>>,
+
+    /// CHECK: Metaplex token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalState<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalState::INIT_SPACE,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Treasury wallet address
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Score oracle signing key
+    pub score_oracle: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"collection_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = card_authority,
+        mint::freeze_authority = card_authority,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// Holds the collection NFT, owned by the card authority PDA
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = card_authority,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA holding mint and update authority over every card
+    #[account(
+        seeds = [b"card_authority"],
+        bump
+    )]
+    pub card_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), collection_mint.key().as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub collection_metadata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), collection_mint.key().as_ref(), b"edition"],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub collection_master_edition: AccountInfo<'info>,
+
+    /// CHECK: Metaplex token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct QueueRoyaltyUpdate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingRoyaltyUpdate::INIT_SPACE,
+        seeds = [b"pending_royalty_update"],
+        bump
+    )]
+    pub pending_royalty_update: Account<'info, PendingRoyaltyUpdate>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRoyaltyUpdate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_royalty_update"],
+        bump
+    )]
+    pub pending_royalty_update: Account<'info, PendingRoyaltyUpdate>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRoyaltyUpdate<'info> {
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_royalty_update"],
+        bump,
+        has_one = queued_by
+    )]
+    pub pending_royalty_update: Account<'info, PendingRoyaltyUpdate>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Gets the pending update's rent back
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetUpdateIntervals<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateScoreOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        constraint = global_state.pending_authority == new_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+        has_one = authority
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -241,7 +1709,86 @@ pub struct CardData {
     pub last_updated: i64,
     pub mint_number: u64,
     pub update_count: u32,
-    pub is_genesis: bool,  // First 1000 cards
+    pub is_genesis: bool,  // First GENESIS_SUPPLY cards
+    pub last_attested_at: i64, // Timestamp of the last oracle attestation
+    pub staking_tier: Tier,    // Owner's staking tier at the last mint or update
+    pub mode: CardMode,
+}
+
+impl CardData {
+    /// Copy everything but the owner and mint from `previous`, for a card
+    /// reminted to a new wallet
+    pub fn carry_over(&mut self, previous: &CardData) {
+        self.degen_score = previous.degen_score;
+        self.total_trades = previous.total_trades;
+        self.total_volume = previous.total_volume;
+        self.win_rate = previous.win_rate;
+        self.last_updated = previous.last_updated;
+        self.mint_number = previous.mint_number;
+        self.update_count = previous.update_count;
+        self.is_genesis = previous.is_genesis;
+        self.last_attested_at = previous.last_attested_at;
+        self.staking_tier = previous.staking_tier;
+        self.mode = previous.mode;
+    }
+}
+
+/// Whether a card can change hands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum CardMode {
+    #[default]
+    Tradeable,  // Regular NFT, free to sell or transfer
+    Soulbound,  // Frozen in the owner's wallet; moves only via burn_and_remint
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ScoreHistory {
+    pub card_data: Pubkey,
+    pub head: u8,  // Slot the next snapshot is written to
+    pub count: u8, // Number of filled slots (up to SCORE_HISTORY_LEN)
+    pub snapshots: [ScoreSnapshot; SCORE_HISTORY_LEN],
+}
+
+impl ScoreHistory {
+    /// Append a snapshot, overwriting the oldest one once the buffer is full
+    pub fn push(&mut self, snapshot: ScoreSnapshot) {
+        self.snapshots[self.head as usize] = snapshot;
+        self.head = ((self.head as usize + 1) % SCORE_HISTORY_LEN) as u8;
+        if (self.count as usize) < SCORE_HISTORY_LEN {
+            self.count += 1;
+        }
+    }
+
+    /// Stored snapshots, oldest first
+    pub fn chronological(&self) -> Vec<ScoreSnapshot> {
+        let count = self.count as usize;
+        let start = (self.head as usize + SCORE_HISTORY_LEN - count) % SCORE_HISTORY_LEN;
+        (0..count)
+            .map(|i| self.snapshots[(start + i) % SCORE_HISTORY_LEN])
+            .collect()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ScoreSnapshot {
+    pub timestamp: i64,
+    pub degen_score: u8,
+    pub win_rate: u8,
+    pub total_trades: u32,
+    pub total_volume: u64,
+}
+
+impl ScoreSnapshot {
+    pub fn from_card(card_data: &CardData) -> Self {
+        Self {
+            timestamp: card_data.last_updated,
+            degen_score: card_data.degen_score,
+            win_rate: card_data.win_rate,
+            total_trades: card_data.total_trades,
+            total_volume: card_data.total_volume,
+        }
+    }
 }
 
 #[account]
@@ -251,6 +1798,213 @@ pub struct GlobalState {
     pub treasury: Pubkey,
     pub total_minted: u64,
     pub royalty_basis_points: u16,
+    pub score_oracle: Pubkey,
+    pub pending_authority: Pubkey, // Default pubkey when no transfer is pending
+    pub timelock_delay: i64, // Seconds between queueing and executing a royalty change
+    pub mint_price: u64,     // In $DEGEN base units, before tier discounts; 0 = free
+    pub tier_discounts_bp: [u16; 3], // Mint price discounts: None / Staker / Whale
+    pub collection_mint: Pubkey, // Default pubkey until `create_collection`
+    pub update_intervals: [i64; 3], // Seconds between score updates: None / Staker / Whale
+    pub pending_timelock_delay: i64, // Shorter delay waiting out the current one; 0 when none
+    pub timelock_delay_eta: i64,     // When `pending_timelock_delay` takes over
+}
+
+impl GlobalState {
+    /// $DEGEN owed for the next card by an owner with `tier`: nothing during
+    /// the Genesis window, otherwise the mint price minus the tier discount
+    pub fn mint_price_for(&self, tier: Tier) -> u64 {
+        if self.total_minted < GENESIS_SUPPLY {
+            return 0;
+        }
+
+        let discount_bp = match tier {
+            Tier::None => self.tier_discounts_bp[0],
+            Tier::Staker => self.tier_discounts_bp[1],
+            Tier::Whale => self.tier_discounts_bp[2],
+        };
+        (self.mint_price as u128 * (10000 - discount_bp as u128) / 10000) as u64
+    }
+
+    /// Minimum time between two score updates of a card whose owner has `tier`
+    pub fn update_interval_for(&self, tier: Tier) -> i64 {
+        match tier {
+            Tier::None => self.update_intervals[0],
+            Tier::Staker => self.update_intervals[1],
+            Tier::Whale => self.update_intervals[2],
+        }
+    }
+
+    /// Delay for changes queued at `now`: a pending shorter delay takes over
+    /// once the delay it replaces has passed
+    pub fn timelock_delay_at(&self, now: i64) -> i64 {
+        if self.pending_timelock_delay > 0 && now >= self.timelock_delay_eta {
+            self.pending_timelock_delay
+        } else {
+            self.timelock_delay
+        }
+    }
+
+    /// Change the delay for changes queued from `now` on and return when it
+    /// applies. A longer delay applies right away; a shorter one only once the
+    /// current delay has passed, so holders always get the notice they were
+    /// promised.
+    pub fn set_timelock_delay(&mut self, timelock_delay: i64, now: i64) -> i64 {
+        let current = self.timelock_delay_at(now);
+        self.timelock_delay = current.max(timelock_delay);
+        if timelock_delay >= current {
+            self.pending_timelock_delay = 0;
+            self.timelock_delay_eta = now;
+        } else {
+            self.pending_timelock_delay = timelock_delay;
+            self.timelock_delay_eta = now + current;
+        }
+        self.timelock_delay_eta
+    }
+}
+
+/// Royalty change waiting for its timelock
+#[account]
+#[derive(InitSpace)]
+pub struct PendingRoyaltyUpdate {
+    pub royalty_basis_points: u16,
+    pub eta: i64,          // Earliest execution time
+    pub queued_by: Pubkey, // Refunded the rent on execution
+}
+
+/// Score payload signed by the oracle; its borsh serialization is the ed25519 message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ScoreAttestation {
+    pub mint: Pubkey,
+    pub degen_score: u8,
+    pub total_trades: u32,
+    pub total_volume: u64,
+    pub win_rate: u8,
+    pub timestamp: i64,
+    pub uri: String,
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct GlobalStateInitialized {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub score_oracle: Pubkey,
+    pub royalty_basis_points: u16,
+}
+
+#[event]
+pub struct CardMinted {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub mint_number: u64,
+    pub degen_score: u8,
+    pub total_trades: u32,
+    pub total_volume: u64,
+    pub win_rate: u8,
+    pub is_genesis: bool,
+    pub staking_tier: Tier,
+    pub mode: CardMode,
+    pub price_paid: u64, // $DEGEN, before transfer fees
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionCreated {
+    pub collection_mint: Pubkey,
+}
+
+#[event]
+pub struct OwnerSynced {
+    pub mint: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct CardReminted {
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub mint_number: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScoreUpdated {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub old_degen_score: u8,
+    pub new_degen_score: u8,
+    pub old_total_trades: u32,
+    pub new_total_trades: u32,
+    pub old_total_volume: u64,
+    pub new_total_volume: u64,
+    pub old_win_rate: u8,
+    pub new_win_rate: u8,
+    pub update_count: u32,
+    pub staking_tier: Tier,
+    pub attested_at: i64,
+    pub uri: String,
+}
+
+#[event]
+pub struct MintPriceUpdated {
+    pub old_mint_price: u64,
+    pub new_mint_price: u64,
+    pub tier_discounts_bp: [u16; 3],
+}
+
+#[event]
+pub struct UpdateIntervalsUpdated {
+    pub old_update_intervals: [i64; 3],
+    pub new_update_intervals: [i64; 3],
+}
+
+#[event]
+pub struct RoyaltiesUpdated {
+    pub old_royalty_basis_points: u16,
+    pub new_royalty_basis_points: u16,
+}
+
+#[event]
+pub struct RoyaltyUpdateQueued {
+    pub royalty_basis_points: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct RoyaltyUpdateCancelled {
+    pub royalty_basis_points: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_timelock_delay: i64,
+    pub new_timelock_delay: i64,
+    pub effective_at: i64, // Later than now when the delay is shortened
+}
+
+#[event]
+pub struct ScoreOracleUpdated {
+    pub old_score_oracle: Pubkey,
+    pub new_score_oracle: Pubkey,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
 }
 
 // ============================================================================
@@ -267,4 +2021,52 @@ pub enum ErrorCode {
 
     #[msg("Royalties cannot exceed 10%")]
     RoyaltiesTooHigh,
+
+    #[msg("Missing score oracle signature")]
+    MissingOracleSignature,
+
+    #[msg("Score oracle signature does not match the update")]
+    InvalidOracleSignature,
+
+    #[msg("Score attestation is stale or already used")]
+    StaleAttestation,
+
+    #[msg("Metadata URI is too long")]
+    UriTooLong,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Timelock has not expired yet")]
+    TimelockNotExpired,
+
+    #[msg("Timelock delay must be between 1 and 30 days")]
+    InvalidTimelockDelay,
+
+    #[msg("Score was updated too recently for this staking tier")]
+    UpdateTooSoon,
+
+    #[msg("Tier discounts cannot exceed 100%")]
+    InvalidDiscount,
+
+    #[msg("Mint payment accounts are required when the mint isn't free")]
+    MissingPaymentAccounts,
+
+    #[msg("Mint payment account does not match the $DEGEN mint or treasury")]
+    InvalidPaymentAccount,
+
+    #[msg("Only soulbound cards are reminted; tradeable cards can be transferred")]
+    NotSoulbound,
+
+    #[msg("Token account does not hold this card")]
+    NotCardHolder,
+
+    #[msg("Collection mint does not match the DegenScore collection")]
+    InvalidCollection,
+
+    #[msg("Update intervals must be non-negative and no longer for higher staking tiers")]
+    InvalidUpdateInterval,
+
+    #[msg("Score attestation is dated in the future")]
+    AttestationFromFuture,
 }
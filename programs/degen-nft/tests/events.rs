@@ -0,0 +1,266 @@
+// DegenNFT Event Tests
+// Emits events through the shared log-capturing syscall stub and decodes them
+// back from the "Program data:" lines, the same way the indexer does.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Event};
+use degen_nft::{
+    AuthorityProposed, AuthorityTransferred, CardMinted, CardMode, CardReminted, CollectionCreated, MintPriceUpdated, OwnerSynced, RoyaltiesUpdated, RoyaltyUpdateCancelled,
+    RoyaltyUpdateQueued, ScoreOracleUpdated, ScoreUpdated, TimelockDelayUpdated, UpdateIntervalsUpdated,
+};
+use event_testing::{capture_logs, decode_event, event_discriminator};
+use staking_interface::Tier;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_discriminators() {
+        assert_eq!(RoyaltyUpdateQueued::DISCRIMINATOR, event_discriminator("RoyaltyUpdateQueued"));
+        assert_eq!(RoyaltyUpdateCancelled::DISCRIMINATOR, event_discriminator("RoyaltyUpdateCancelled"));
+        assert_eq!(TimelockDelayUpdated::DISCRIMINATOR, event_discriminator("TimelockDelayUpdated"));
+        assert_eq!(AuthorityProposed::DISCRIMINATOR, event_discriminator("AuthorityProposed"));
+        assert_eq!(AuthorityTransferred::DISCRIMINATOR, event_discriminator("AuthorityTransferred"));
+        assert_eq!(CardMinted::DISCRIMINATOR, event_discriminator("CardMinted"));
+        assert_eq!(CardReminted::DISCRIMINATOR, event_discriminator("CardReminted"));
+        assert_eq!(OwnerSynced::DISCRIMINATOR, event_discriminator("OwnerSynced"));
+        assert_eq!(CollectionCreated::DISCRIMINATOR, event_discriminator("CollectionCreated"));
+        assert_eq!(ScoreUpdated::DISCRIMINATOR, event_discriminator("ScoreUpdated"));
+        assert_eq!(RoyaltiesUpdated::DISCRIMINATOR, event_discriminator("RoyaltiesUpdated"));
+        assert_eq!(ScoreOracleUpdated::DISCRIMINATOR, event_discriminator("ScoreOracleUpdated"));
+        assert_eq!(MintPriceUpdated::DISCRIMINATOR, event_discriminator("MintPriceUpdated"));
+        assert_eq!(UpdateIntervalsUpdated::DISCRIMINATOR, event_discriminator("UpdateIntervalsUpdated"));
+    }
+
+    #[test]
+    fn test_card_minted_event() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(CardMinted {
+                owner,
+                mint,
+                mint_number: 42,
+                degen_score: 88,
+                total_trades: 1_500,
+                total_volume: 250_000_000,
+                win_rate: 64,
+                is_genesis: true,
+                staking_tier: Tier::Whale,
+                mode: CardMode::Soulbound,
+                price_paid: 0,
+                timestamp: 1_700_000_000,
+            });
+        });
+
+        let event: CardMinted = decode_event(&logs).expect("CardMinted not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.mint, mint);
+        assert_eq!(event.mint_number, 42);
+        assert_eq!(event.degen_score, 88);
+        assert_eq!(event.total_trades, 1_500);
+        assert_eq!(event.total_volume, 250_000_000);
+        assert_eq!(event.win_rate, 64);
+        assert!(event.is_genesis);
+        assert_eq!(event.staking_tier, Tier::Whale);
+        assert_eq!(event.mode, CardMode::Soulbound);
+        assert_eq!(event.price_paid, 0);
+        assert_eq!(event.timestamp, 1_700_000_000);
+
+        // 8 discriminator + 2 pubkeys + u64 + u8 + u32 + u64 + u8 + bool + tier + mode + u64 + i64
+        assert_eq!(event.data().len(), 8 + 64 + 8 + 1 + 4 + 8 + 1 + 1 + 1 + 1 + 8 + 8);
+    }
+
+    #[test]
+    fn test_collection_created_event() {
+        let collection_mint = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(CollectionCreated { collection_mint });
+        });
+
+        let event: CollectionCreated = decode_event(&logs).expect("CollectionCreated not found in logs");
+        assert_eq!(event.collection_mint, collection_mint);
+    }
+
+    #[test]
+    fn test_owner_synced_event() {
+        let mint = Pubkey::new_unique();
+        let seller = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(OwnerSynced {
+                mint,
+                old_owner: seller,
+                new_owner: buyer,
+            });
+        });
+
+        let event: OwnerSynced = decode_event(&logs).expect("OwnerSynced not found in logs");
+        assert_eq!(event.mint, mint);
+        assert_eq!(event.old_owner, seller);
+        assert_eq!(event.new_owner, buyer);
+    }
+
+    #[test]
+    fn test_card_reminted_event() {
+        let old_owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let old_mint = Pubkey::new_unique();
+        let new_mint = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(CardReminted {
+                old_owner,
+                new_owner,
+                old_mint,
+                new_mint,
+                mint_number: 42,
+                timestamp: 1_700_000_000,
+            });
+        });
+
+        let event: CardReminted = decode_event(&logs).expect("CardReminted not found in logs");
+        assert_eq!(event.old_owner, old_owner);
+        assert_eq!(event.new_owner, new_owner);
+        assert_eq!(event.old_mint, old_mint);
+        assert_eq!(event.new_mint, new_mint);
+        assert_eq!(event.mint_number, 42);
+        assert_eq!(event.timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_score_updated_event() {
+        let mint = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(ScoreUpdated {
+                mint,
+                owner: Pubkey::new_unique(),
+                old_degen_score: 70,
+                new_degen_score: 91,
+                old_total_trades: 100,
+                new_total_trades: 140,
+                old_total_volume: 1_000,
+                new_total_volume: 5_000,
+                old_win_rate: 50,
+                new_win_rate: 58,
+                update_count: 3,
+                staking_tier: Tier::Staker,
+                attested_at: 1_700_000_100,
+                uri: "https://api.degenscore.com/metadata/7.json".to_string(),
+            });
+        });
+
+        let event: ScoreUpdated = decode_event(&logs).expect("ScoreUpdated not found in logs");
+        assert_eq!(event.mint, mint);
+        assert_eq!((event.old_degen_score, event.new_degen_score), (70, 91));
+        assert_eq!((event.old_total_trades, event.new_total_trades), (100, 140));
+        assert_eq!((event.old_total_volume, event.new_total_volume), (1_000, 5_000));
+        assert_eq!((event.old_win_rate, event.new_win_rate), (50, 58));
+        assert_eq!(event.update_count, 3);
+        assert_eq!(event.staking_tier, Tier::Staker);
+        assert_eq!(event.attested_at, 1_700_000_100);
+        assert_eq!(event.uri, "https://api.degenscore.com/metadata/7.json");
+    }
+
+    #[test]
+    fn test_admin_events() {
+        let new_score_oracle = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(RoyaltiesUpdated {
+                old_royalty_basis_points: 500,
+                new_royalty_basis_points: 750,
+            });
+            emit!(ScoreOracleUpdated {
+                old_score_oracle: Pubkey::default(),
+                new_score_oracle,
+            });
+            emit!(MintPriceUpdated {
+                old_mint_price: 0,
+                new_mint_price: 500_000_000_000,
+                tier_discounts_bp: [0, 5000, 10000],
+            });
+            emit!(UpdateIntervalsUpdated {
+                old_update_intervals: [0, 0, 0],
+                new_update_intervals: [24 * 3600, 6 * 3600, 3600],
+            });
+        });
+
+        let royalties: RoyaltiesUpdated = decode_event(&logs).expect("RoyaltiesUpdated not found in logs");
+        assert_eq!(royalties.old_royalty_basis_points, 500);
+        assert_eq!(royalties.new_royalty_basis_points, 750);
+
+        let oracle: ScoreOracleUpdated = decode_event(&logs).expect("ScoreOracleUpdated not found in logs");
+        assert_eq!(oracle.new_score_oracle, new_score_oracle);
+
+        let price: MintPriceUpdated = decode_event(&logs).expect("MintPriceUpdated not found in logs");
+        assert_eq!(price.old_mint_price, 0);
+        assert_eq!(price.new_mint_price, 500_000_000_000);
+        assert_eq!(price.tier_discounts_bp, [0, 5000, 10000]);
+
+        let intervals: UpdateIntervalsUpdated = decode_event(&logs).expect("UpdateIntervalsUpdated not found in logs");
+        assert_eq!(intervals.new_update_intervals, [24 * 3600, 6 * 3600, 3600]);
+    }
+
+    #[test]
+    fn test_authority_events() {
+        let authority = Pubkey::new_unique();
+        let pending_authority = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(AuthorityProposed {
+                authority,
+                pending_authority,
+            });
+            emit!(AuthorityTransferred {
+                old_authority: authority,
+                new_authority: pending_authority,
+            });
+        });
+
+        let proposed: AuthorityProposed = decode_event(&logs).expect("AuthorityProposed not found in logs");
+        assert_eq!(proposed.authority, authority);
+        assert_eq!(proposed.pending_authority, pending_authority);
+
+        let transferred: AuthorityTransferred = decode_event(&logs).expect("AuthorityTransferred not found in logs");
+        assert_eq!(transferred.old_authority, authority);
+        assert_eq!(transferred.new_authority, pending_authority);
+    }
+
+    #[test]
+    fn test_timelock_events() {
+        let logs = capture_logs(|| {
+            emit!(RoyaltyUpdateQueued {
+                royalty_basis_points: 750,
+                eta: 1_700_172_800,
+            });
+            emit!(RoyaltyUpdateCancelled {
+                royalty_basis_points: 750,
+                eta: 1_700_172_800,
+            });
+            emit!(TimelockDelayUpdated {
+                old_timelock_delay: 48 * 3600,
+                new_timelock_delay: 24 * 3600,
+                effective_at: 1_700_000_000,
+            });
+        });
+
+        let queued: RoyaltyUpdateQueued = decode_event(&logs).expect("RoyaltyUpdateQueued not found in logs");
+        assert_eq!(queued.royalty_basis_points, 750);
+        assert_eq!(queued.eta, 1_700_172_800);
+
+        let cancelled: RoyaltyUpdateCancelled = decode_event(&logs).expect("RoyaltyUpdateCancelled not found in logs");
+        assert_eq!(cancelled.royalty_basis_points, 750);
+        assert_eq!(cancelled.eta, queued.eta);
+
+        let delay: TimelockDelayUpdated = decode_event(&logs).expect("TimelockDelayUpdated not found in logs");
+        assert_eq!(delay.old_timelock_delay, 48 * 3600);
+        assert_eq!(delay.new_timelock_delay, 24 * 3600);
+        assert_eq!(delay.effective_at, 1_700_000_000);
+    }
+}
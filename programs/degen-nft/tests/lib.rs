@@ -2,11 +2,114 @@
 // Tests NFT minting, metadata, and access control
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::{spl_token, TokenAccount};
+use degen_nft::{
+    check_attestation_time, holds_card, is_collection_member, is_verified_card, verify_ed25519_instruction, CardData, CardMode, GlobalState, ScoreAttestation, ScoreHistory,
+    ScoreSnapshot, GENESIS_SUPPLY, SCORE_HISTORY_LEN,
+};
+use mpl_token_metadata::pda::find_metadata_account;
+use mpl_token_metadata::state::{Collection, Data, Key, Metadata};
+use staking_interface::Tier;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Build an ed25519 program instruction the same way the SDK does
+    /// (public key at 16, signature at 48, message at 112).
+    fn ed25519_ix(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Instruction {
+        let mut data = vec![1u8, 0u8];
+        for value in [48u16, instruction_index, 16, instruction_index, 112, message.len() as u16, instruction_index] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7u8; 64]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn empty_history() -> ScoreHistory {
+        ScoreHistory {
+            card_data: Pubkey::new_unique(),
+            head: 0,
+            count: 0,
+            snapshots: [ScoreSnapshot::default(); SCORE_HISTORY_LEN],
+        }
+    }
+
+    /// Global state after `total_minted` cards, charging `mint_price` with the default discounts
+    fn global_state(total_minted: u64, mint_price: u64) -> GlobalState {
+        GlobalState {
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            total_minted,
+            royalty_basis_points: 500,
+            score_oracle: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            timelock_delay: degen_nft::DEFAULT_TIMELOCK_DELAY,
+            mint_price,
+            tier_discounts_bp: [0, 5000, 10000],
+            collection_mint: Pubkey::new_unique(),
+            update_intervals: [0, 0, 0],
+            pending_timelock_delay: 0,
+            timelock_delay_eta: 0,
+        }
+    }
+
+    /// Initialized SPL token account of `owner` holding `amount` of `mint`
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccount {
+        let account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(account, &mut data).unwrap();
+        TokenAccount::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    /// Metaplex metadata of `mint` with the given collection membership
+    fn card_metadata(mint: Pubkey, collection: Option<Collection>) -> Metadata {
+        Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint,
+            data: Data {
+                name: "DegenScore #1".to_string(),
+                symbol: "DEGEN".to_string(),
+                uri: "https://degenscore.com/card/1.json".to_string(),
+                seller_fee_basis_points: 500,
+                creators: None,
+            },
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        }
+    }
+
+    fn snapshot(timestamp: i64) -> ScoreSnapshot {
+        ScoreSnapshot {
+            timestamp,
+            degen_score: (timestamp % 101) as u8,
+            ..ScoreSnapshot::default()
+        }
+    }
+
     #[test]
     fn test_nft_initialization() {
         // Tests that NFT collection can be initialized
@@ -168,4 +271,278 @@ mod tests {
         assert!(edition > 0);
         assert!(edition <= max_edition);
     }
+
+    #[test]
+    fn test_score_attestation_message_layout() {
+        // The oracle signs mint | score | trades | volume | win rate | timestamp | uri
+        let uri = "https://api.degenscore.com/metadata/42.json".to_string();
+        let attestation = ScoreAttestation {
+            mint: Pubkey::new_unique(),
+            degen_score: 87,
+            total_trades: 1_234,
+            total_volume: 5_000_000,
+            win_rate: 62,
+            timestamp: 1_700_000_000,
+            uri: uri.clone(),
+        };
+        let message = attestation.try_to_vec().unwrap();
+
+        assert_eq!(message.len(), 32 + 1 + 4 + 8 + 1 + 8 + 4 + uri.len());
+        assert_eq!(&message[..32], attestation.mint.as_ref());
+        assert_eq!(message[32], 87);
+        assert_eq!(&message[46..54], &1_700_000_000i64.to_le_bytes());
+        assert_eq!(&message[58..], uri.as_bytes());
+    }
+
+    #[test]
+    fn test_attestation_time_window() {
+        let now = 1_700_000_000;
+
+        assert!(check_attestation_time(now, now - 3600, now).is_ok());
+        assert!(check_attestation_time(now - 300, 0, now).is_ok());
+        assert!(check_attestation_time(now + 60, 0, now).is_ok());
+
+        // Replayed, too old, or too far ahead of the cluster clock
+        assert!(check_attestation_time(now, now, now).is_err());
+        assert!(check_attestation_time(now - 301, 0, now).is_err());
+        assert!(check_attestation_time(now + 61, 0, now).is_err());
+    }
+
+    #[test]
+    fn test_oracle_signature_accepted() {
+        let oracle = Pubkey::new_unique();
+        let message = b"degen score attestation";
+
+        let ix = ed25519_ix(&oracle, message, u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_signature_rejects_wrong_signer_or_message() {
+        let oracle = Pubkey::new_unique();
+        let impostor = Pubkey::new_unique();
+        let message = b"degen score attestation";
+
+        let ix = ed25519_ix(&impostor, message, u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+
+        let ix = ed25519_ix(&oracle, b"fabricated score", u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+    }
+
+    #[test]
+    fn test_oracle_signature_rejects_foreign_offsets() {
+        // Offsets pointing at another instruction could reference arbitrary data
+        let oracle = Pubkey::new_unique();
+        let message = b"degen score attestation";
+
+        let ix = ed25519_ix(&oracle, message, 0);
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+
+        let mut ix = ed25519_ix(&oracle, message, u16::MAX);
+        ix.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+    }
+
+    #[test]
+    fn test_score_history_partial_fill() {
+        let mut history = empty_history();
+        assert!(history.chronological().is_empty());
+
+        for timestamp in 1..=3 {
+            history.push(snapshot(timestamp));
+        }
+
+        let timestamps: Vec<i64> = history.chronological().iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_score_history_wraps_oldest_first() {
+        // Overfill the ring buffer: only the latest SCORE_HISTORY_LEN remain, oldest first
+        let mut history = empty_history();
+        let total = SCORE_HISTORY_LEN as i64 + 5;
+        for timestamp in 1..=total {
+            history.push(snapshot(timestamp));
+        }
+
+        let series = history.chronological();
+        assert_eq!(series.len(), SCORE_HISTORY_LEN);
+        assert_eq!(series.first().unwrap().timestamp, 6);
+        assert_eq!(series.last().unwrap().timestamp, total);
+        assert!(series.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_update_interval_shrinks_with_staking_tier() {
+        // No limit by default, as before staking perks
+        let mut global_state = global_state(0, 0);
+        assert_eq!(global_state.update_interval_for(Tier::None), 0);
+        assert_eq!(global_state.update_interval_for(Tier::Whale), 0);
+
+        global_state.update_intervals = [24 * 3600, 6 * 3600, 3600];
+        assert_eq!(global_state.update_interval_for(Tier::None), 24 * 3600);
+        assert_eq!(global_state.update_interval_for(Tier::Staker), 6 * 3600);
+        assert_eq!(global_state.update_interval_for(Tier::Whale), 3600);
+
+        // Owners who never staked have no summary account
+        assert_eq!(global_state.update_interval_for(staking_interface::tier_of(None)), 24 * 3600);
+    }
+
+    #[test]
+    fn test_shorter_timelock_delay_waits_out_current_one() {
+        let day = 24 * 3600;
+        let mut state = global_state(0, 0);
+
+        // Dropping from 48h to 24h: royalty changes queued in the next 48h still wait 48h
+        assert_eq!(state.set_timelock_delay(day, 1_000), 1_000 + 2 * day);
+        assert_eq!(state.timelock_delay_at(1_000 + 2 * day - 1), 2 * day);
+        assert_eq!(state.timelock_delay_at(1_000 + 2 * day), day);
+
+        // Shortening again before then waits out the 48h still in force
+        let mut state = global_state(0, 0);
+        state.set_timelock_delay(day, 1_000);
+        assert_eq!(state.set_timelock_delay(36 * 3600, 1_000 + day), 1_000 + 3 * day);
+        assert_eq!(state.timelock_delay_at(1_000 + 3 * day), 36 * 3600);
+    }
+
+    #[test]
+    fn test_mint_price_discounted_by_tier() {
+        let state = global_state(GENESIS_SUPPLY, 1_000_000);
+
+        assert_eq!(state.mint_price_for(Tier::None), 1_000_000);
+        assert_eq!(state.mint_price_for(Tier::Staker), 500_000);
+        assert_eq!(state.mint_price_for(Tier::Whale), 0);
+    }
+
+    #[test]
+    fn test_genesis_mints_are_free() {
+        // The last Genesis card is free, the first one after it is not
+        assert_eq!(global_state(GENESIS_SUPPLY - 1, 1_000_000).mint_price_for(Tier::None), 0);
+        assert_eq!(global_state(GENESIS_SUPPLY, 1_000_000).mint_price_for(Tier::None), 1_000_000);
+
+        // No price configured
+        assert_eq!(global_state(GENESIS_SUPPLY + 10, 0).mint_price_for(Tier::None), 0);
+    }
+
+    #[test]
+    fn test_remint_carries_card_over() {
+        let old_card = CardData {
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            degen_score: 95,
+            total_trades: 4_200,
+            total_volume: 9_000_000,
+            win_rate: 71,
+            last_updated: 1_700_000_000,
+            mint_number: 7,
+            update_count: 12,
+            is_genesis: true,
+            last_attested_at: 1_699_999_950,
+            staking_tier: Tier::Staker,
+            mode: CardMode::Soulbound,
+        };
+        let new_owner = Pubkey::new_unique();
+        let new_mint = Pubkey::new_unique();
+
+        let mut new_card = CardData {
+            owner: new_owner,
+            mint: new_mint,
+            degen_score: 0,
+            total_trades: 0,
+            total_volume: 0,
+            win_rate: 0,
+            last_updated: 0,
+            mint_number: 0,
+            update_count: 0,
+            is_genesis: false,
+            last_attested_at: 0,
+            staking_tier: Tier::None,
+            mode: CardMode::default(),
+        };
+        new_card.carry_over(&old_card);
+
+        assert_eq!(new_card.owner, new_owner);
+        assert_eq!(new_card.mint, new_mint);
+        assert_eq!(new_card.degen_score, 95);
+        assert_eq!(new_card.total_volume, 9_000_000);
+        assert_eq!(new_card.mint_number, 7);
+        assert!(new_card.is_genesis);
+        assert_eq!(new_card.mode, CardMode::Soulbound);
+
+        // The rate limit and replay protection don't reset with the new wallet
+        assert_eq!(new_card.last_updated, old_card.last_updated);
+        assert_eq!(new_card.last_attested_at, old_card.last_attested_at);
+    }
+
+    #[test]
+    fn test_card_holder_is_whoever_holds_the_token() {
+        let mint = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+
+        assert!(holds_card(&token_account(mint, buyer, 1), &mint));
+
+        // Sold on (empty account) or a different card
+        assert!(!holds_card(&token_account(mint, buyer, 0), &mint));
+        assert!(!holds_card(&token_account(Pubkey::new_unique(), buyer, 1), &mint));
+    }
+
+    #[test]
+    fn test_verified_collection_membership() {
+        let collection_mint = Pubkey::new_unique();
+        let member = |key, verified| card_metadata(Pubkey::new_unique(), Some(Collection { verified, key }));
+
+        assert!(is_verified_card(&member(collection_mint, true), &collection_mint));
+
+        // Unverified, another collection, or no collection at all
+        assert!(!is_verified_card(&member(collection_mint, false), &collection_mint));
+        assert!(!is_verified_card(&member(Pubkey::new_unique(), true), &collection_mint));
+        assert!(!is_verified_card(&card_metadata(Pubkey::new_unique(), None), &collection_mint));
+    }
+
+    #[test]
+    fn test_collection_member_lookup_checks_metadata_account() {
+        let collection_mint = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let metadata = card_metadata(
+            mint,
+            Some(Collection {
+                verified: true,
+                key: collection_mint,
+            }),
+        );
+        let mut data = vec![0u8; mpl_token_metadata::state::MAX_METADATA_LEN];
+        metadata.save(&mut data).unwrap();
+
+        let metadata_key = find_metadata_account(&mint).0;
+        let mut lamports = 1_000_000;
+        let info = AccountInfo::new(
+            &metadata_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+        assert!(is_collection_member(&info, &mint, &collection_mint).unwrap());
+
+        // Same data passed for another mint, or owned by another program
+        assert!(!is_collection_member(&info, &Pubkey::new_unique(), &collection_mint).unwrap());
+        let mut fake_data = data.clone();
+        let mut fake_lamports = 1_000_000;
+        let fake_owner = Pubkey::new_unique();
+        let fake = AccountInfo::new(
+            &metadata_key,
+            false,
+            false,
+            &mut fake_lamports,
+            &mut fake_data,
+            &fake_owner,
+            false,
+            0,
+        );
+        assert!(!is_collection_member(&fake, &mint, &collection_mint).unwrap());
+    }
 }
@@ -7,15 +7,34 @@
  * - Mintable SPL token
  * - Transfer fees (5% burn, 5% to treasury)
  * - Anti-whale protection (max 1% of supply per wallet)
+ * - Exemption registry for treasury, staking and pool accounts
  * - Pausable in emergencies
+ * - Timelocked fee changes
  * - Upgradeable authority
+ *
+ * Builds:
+ * - `classic-spl` (default): SPL Token mint, fees only via `transfer_with_fees`
+ * - Token-2022 (`--no-default-features`): TransferFee extension withholds the
+ *   fees on every transfer and a transfer hook enforces the max-wallet rule
  */
 
 use anchor_lang::prelude::*;
+#[cfg(feature = "classic-spl")]
 use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
 
+#[cfg(not(feature = "classic-spl"))]
+mod token_2022;
+#[cfg(not(feature = "classic-spl"))]
+pub use token_2022::*;
+
 declare_id!("DegenScore11111111111111111111111111111111");
 
+// Fee changes wait this long between queueing and execution (48 hours)
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 48 * 3600;
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 3600;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 3600;
+
+#[cfg(feature = "classic-spl")]
 #[program]
 pub mod degen_token {
     use super::*;
@@ -24,6 +43,7 @@ pub mod degen_token {
     pub fn initialize(ctx: Context<Initialize>, decimals: u8) -> Result<()> {
         let token_data = &mut ctx.accounts.token_data;
         token_data.authority = ctx.accounts.authority.key();
+        token_data.mint = ctx.accounts.mint.key();
         token_data.total_supply = 1_000_000_000 * 10u64.pow(decimals as u32); // 1B tokens
         token_data.circulating_supply = 0;
         token_data.decimals = decimals;
@@ -32,6 +52,15 @@ pub mod degen_token {
         token_data.treasury_rate = 500; // 5%
         token_data.treasury = ctx.accounts.treasury.key();
         token_data.max_wallet_percent = 100; // 1% (in basis points: 100/10000)
+        token_data.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+
+        emit!(TokenInitialized {
+            authority: token_data.authority,
+            mint: ctx.accounts.mint.key(),
+            treasury: token_data.treasury,
+            decimals,
+            total_supply: token_data.total_supply,
+        });
 
         msg!("$DEGEN Token initialized with {} decimals", decimals);
         msg!("Total supply: {} tokens", token_data.total_supply);
@@ -64,6 +93,12 @@ pub mod degen_token {
 
         token_data.circulating_supply += amount;
 
+        emit!(TokensMinted {
+            destination: ctx.accounts.destination.key(),
+            amount,
+            circulating_supply: token_data.circulating_supply,
+        });
+
         msg!("Minted {} tokens. New circulating supply: {}", amount, token_data.circulating_supply);
 
         Ok(())
@@ -75,19 +110,34 @@ pub mod degen_token {
 
         require!(!token_data.is_paused, ErrorCode::ProgramPaused);
 
+        // Exemptions are looked up by the destination token account
+        let (fee_exempt, cap_exempt) = ctx
+            .accounts
+            .exemption
+            .as_ref()
+            .map_or((false, false), |exemption| (exemption.fee_exempt, exemption.cap_exempt));
+
         // Calculate fees
-        let burn_amount = amount * token_data.burn_rate as u64 / 10000;
-        let treasury_amount = amount * token_data.treasury_rate as u64 / 10000;
+        let (burn_amount, treasury_amount) = if fee_exempt {
+            (0, 0)
+        } else {
+            (
+                amount * token_data.burn_rate as u64 / 10000,
+                amount * token_data.treasury_rate as u64 / 10000,
+            )
+        };
         let recipient_amount = amount - burn_amount - treasury_amount;
 
         // Anti-whale check: recipient can't hold more than max_wallet_percent
-        let max_wallet_amount = token_data.total_supply * token_data.max_wallet_percent as u64 / 10000;
-        let recipient_new_balance = ctx.accounts.to.amount + recipient_amount;
-
-        require!(
-            recipient_new_balance <= max_wallet_amount,
-            ErrorCode::ExceedsMaxWalletSize
-        );
+        if !cap_exempt {
+            let max_wallet_amount = token_data.total_supply * token_data.max_wallet_percent as u64 / 10000;
+            let recipient_new_balance = ctx.accounts.to.amount + recipient_amount;
+
+            require!(
+                recipient_new_balance <= max_wallet_amount,
+                ErrorCode::ExceedsMaxWalletSize
+            );
+        }
 
         // Transfer to recipient
         let cpi_accounts = Transfer {
@@ -123,6 +173,15 @@ pub mod degen_token {
             token::burn(cpi_ctx, burn_amount)?;
         }
 
+        emit!(TokensTransferred {
+            from: ctx.accounts.from.key(),
+            to: ctx.accounts.to.key(),
+            amount,
+            recipient_amount,
+            treasury_amount,
+            burn_amount,
+        });
+
         msg!(
             "Transfer: {} to recipient, {} to treasury, {} burned",
             recipient_amount,
@@ -136,31 +195,296 @@ pub mod degen_token {
     /// Pause/unpause the token (emergency only)
     pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
         let token_data = &mut ctx.accounts.token_data;
+        let was_paused = token_data.is_paused;
         token_data.is_paused = paused;
 
+        emit!(PauseUpdated {
+            was_paused,
+            is_paused: paused,
+        });
+
         msg!("Token paused status set to: {}", paused);
 
         Ok(())
     }
 
-    /// Update fee rates (authority only)
-    pub fn update_fees(ctx: Context<UpdateFees>, burn_rate: u16, treasury_rate: u16) -> Result<()> {
+    /// Schedule a fee change, executable once the timelock delay has passed (authority only)
+    pub fn queue_fee_update(ctx: Context<QueueFeeUpdate>, burn_rate: u16, treasury_rate: u16) -> Result<()> {
         require!(burn_rate + treasury_rate <= 2000, ErrorCode::FeesTooHigh); // Max 20% total fees
 
+        let now = Clock::get()?.unix_timestamp;
+        let eta = now + ctx.accounts.token_data.timelock_delay_at(now);
+        let pending_fee_update = &mut ctx.accounts.pending_fee_update;
+        pending_fee_update.burn_rate = burn_rate;
+        pending_fee_update.treasury_rate = treasury_rate;
+        pending_fee_update.eta = eta;
+        pending_fee_update.queued_by = ctx.accounts.authority.key();
+
+        emit!(FeeUpdateQueued {
+            burn_rate,
+            treasury_rate,
+            eta,
+        });
+
+        msg!(
+            "Fee update queued - Burn: {}%, Treasury: {}%, executable at {}",
+            burn_rate / 100,
+            treasury_rate / 100,
+            eta
+        );
+
+        Ok(())
+    }
+
+    /// Drop a queued fee change before it is executed (authority only)
+    pub fn cancel_fee_update(ctx: Context<CancelFeeUpdate>) -> Result<()> {
+        let pending_fee_update = &ctx.accounts.pending_fee_update;
+
+        emit!(FeeUpdateCancelled {
+            burn_rate: pending_fee_update.burn_rate,
+            treasury_rate: pending_fee_update.treasury_rate,
+            eta: pending_fee_update.eta,
+        });
+
+        msg!("Fee update cancelled");
+
+        Ok(())
+    }
+
+    /// Change the delay applied to newly queued changes (authority only). A
+    /// shorter delay only applies once the current one has passed.
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+        require!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            ErrorCode::InvalidTimelockDelay
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let token_data = &mut ctx.accounts.token_data;
+        let old_timelock_delay = token_data.timelock_delay_at(now);
+        let effective_at = token_data.set_timelock_delay(timelock_delay, now);
+
+        emit!(TimelockDelayUpdated {
+            old_timelock_delay,
+            new_timelock_delay: timelock_delay,
+            effective_at,
+        });
+
+        msg!("Timelock delay set to {} seconds from {}", timelock_delay, effective_at);
+
+        Ok(())
+    }
+
+    /// Apply a queued fee change once its timelock has passed (anyone)
+    pub fn execute_fee_update(ctx: Context<ExecuteFeeUpdate>) -> Result<()> {
+        let pending_fee_update = &ctx.accounts.pending_fee_update;
+        require!(
+            Clock::get()?.unix_timestamp >= pending_fee_update.eta,
+            ErrorCode::TimelockNotExpired
+        );
+
+        let token_data = &mut ctx.accounts.token_data;
+        let old_burn_rate = token_data.burn_rate;
+        let old_treasury_rate = token_data.treasury_rate;
+        token_data.burn_rate = pending_fee_update.burn_rate;
+        token_data.treasury_rate = pending_fee_update.treasury_rate;
+
+        emit!(FeesUpdated {
+            old_burn_rate,
+            new_burn_rate: token_data.burn_rate,
+            old_treasury_rate,
+            new_treasury_rate: token_data.treasury_rate,
+        });
+
+        msg!(
+            "Fees updated - Burn: {}%, Treasury: {}%",
+            token_data.burn_rate / 100,
+            token_data.treasury_rate / 100
+        );
+
+        Ok(())
+    }
+
+    /// Exempt a token account from fees and/or the max wallet limit (authority only)
+    pub fn add_exemption(ctx: Context<AddExemption>, fee_exempt: bool, cap_exempt: bool) -> Result<()> {
+        let exemption = &mut ctx.accounts.exemption;
+        exemption.token_account = ctx.accounts.token_account.key();
+        exemption.fee_exempt = fee_exempt;
+        exemption.cap_exempt = cap_exempt;
+
+        emit!(ExemptionAdded {
+            token_account: exemption.token_account,
+            fee_exempt,
+            cap_exempt,
+        });
+
+        msg!(
+            "Exemption added for {} - Fees: {}, Max wallet: {}",
+            exemption.token_account,
+            fee_exempt,
+            cap_exempt
+        );
+
+        Ok(())
+    }
+
+    /// Remove a token account from the exemption registry (authority only)
+    pub fn remove_exemption(ctx: Context<RemoveExemption>) -> Result<()> {
+        emit!(ExemptionRemoved {
+            token_account: ctx.accounts.exemption.token_account,
+        });
+
+        msg!("Exemption removed for {}", ctx.accounts.exemption.token_account);
+
+        Ok(())
+    }
+
+    /// Propose a new authority; it takes over once it calls `accept_authority`.
+    /// Proposing `Pubkey::default()` cancels a pending proposal.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        token_data.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: token_data.authority,
+            pending_authority: new_authority,
+        });
+
+        msg!("Authority transfer proposed to: {}", new_authority);
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (proposed authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let old_authority = token_data.authority;
+        token_data.authority = token_data.pending_authority;
+        token_data.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: token_data.authority,
+        });
+
+        msg!("Authority transferred to: {}", token_data.authority);
+
+        Ok(())
+    }
+
+    /// Give up admin control for good (authority only)
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
         let token_data = &mut ctx.accounts.token_data;
-        token_data.burn_rate = burn_rate;
-        token_data.treasury_rate = treasury_rate;
+        let old_authority = token_data.authority;
+        token_data.authority = Pubkey::default();
+        token_data.pending_authority = Pubkey::default();
 
-        msg!("Fees updated - Burn: {}%, Treasury: {}%", burn_rate / 100, treasury_rate / 100);
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: Pubkey::default(),
+        });
+
+        msg!("Authority renounced");
 
         Ok(())
     }
 }
 
+#[cfg(not(feature = "classic-spl"))]
+#[program]
+pub mod degen_token {
+    use super::*;
+
+    /// Initialize the $DEGEN Token-2022 mint with transfer fee and transfer hook extensions
+    pub fn initialize(ctx: Context<Initialize>, decimals: u8) -> Result<()> {
+        token_2022::initialize(ctx, decimals)
+    }
+
+    /// Mint new tokens (only authority)
+    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
+        token_2022::mint_tokens(ctx, amount)
+    }
+
+    /// Withdraw fees withheld at the mint and split them into burn and treasury
+    pub fn withdraw_withheld<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawWithheld<'info>>) -> Result<()> {
+        token_2022::withdraw_withheld(ctx)
+    }
+
+    /// Pause/unpause the token (emergency only)
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        token_2022::set_paused(ctx, paused)
+    }
+
+    /// Schedule a fee change, executable once the timelock delay has passed (authority only)
+    pub fn queue_fee_update(ctx: Context<QueueFeeUpdate>, burn_rate: u16, treasury_rate: u16) -> Result<()> {
+        token_2022::queue_fee_update(ctx, burn_rate, treasury_rate)
+    }
+
+    /// Drop a queued fee change before it is executed (authority only)
+    pub fn cancel_fee_update(ctx: Context<CancelFeeUpdate>) -> Result<()> {
+        token_2022::cancel_fee_update(ctx)
+    }
+
+    /// Apply a queued fee change and the mint's transfer fee once its timelock has passed (anyone)
+    pub fn execute_fee_update(ctx: Context<ExecuteFeeUpdate>) -> Result<()> {
+        token_2022::execute_fee_update(ctx)
+    }
+
+    /// Change the delay applied to newly queued changes (authority only). A
+    /// shorter delay only applies once the current one has passed.
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+        token_2022::set_timelock_delay(ctx, timelock_delay)
+    }
+
+    /// Exempt a token account from the max wallet limit (authority only). The
+    /// transfer hook reads `cap_exempt`; `fee_exempt` has no effect here since
+    /// the TransferFee extension withholds the fee on every transfer.
+    pub fn add_exemption(ctx: Context<AddExemption>, fee_exempt: bool, cap_exempt: bool) -> Result<()> {
+        token_2022::add_exemption(ctx, fee_exempt, cap_exempt)
+    }
+
+    /// Remove a token account from the exemption registry (authority only)
+    pub fn remove_exemption(ctx: Context<RemoveExemption>) -> Result<()> {
+        token_2022::remove_exemption(ctx)
+    }
+
+    /// Propose a new authority; it takes over once it calls `accept_authority`
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        token_2022::propose_authority(ctx, new_authority)
+    }
+
+    /// Accept a pending authority transfer (proposed authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        token_2022::accept_authority(ctx)
+    }
+
+    /// Give up admin control for good (authority only)
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        token_2022::renounce_authority(ctx)
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Split collected fees between burn and treasury in proportion to their rates.
+/// Rounding dust goes to the treasury.
+pub fn split_fees(amount: u64, burn_rate: u16, treasury_rate: u16) -> (u64, u64) {
+    let total_rate = burn_rate as u128 + treasury_rate as u128;
+    if total_rate == 0 {
+        return (0, amount);
+    }
+
+    let burn_amount = (amount as u128 * burn_rate as u128 / total_rate) as u64;
+    (burn_amount, amount - burn_amount)
+}
+
 // ============================================================================
 // CONTEXTS
 // ============================================================================
 
+#[cfg(feature = "classic-spl")]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -186,6 +510,7 @@ pub struct Initialize<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[cfg(feature = "classic-spl")]
 #[derive(Accounts)]
 pub struct MintTokens<'info> {
     #[account(
@@ -208,6 +533,7 @@ pub struct MintTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[cfg(feature = "classic-spl")]
 #[derive(Accounts)]
 pub struct TransferWithFees<'info> {
     #[account(
@@ -230,6 +556,13 @@ pub struct TransferWithFees<'info> {
 
     pub from_authority: Signer<'info>,
 
+    /// Registry entry of the destination, if it has one
+    #[account(
+        seeds = [b"exemption", to.key().as_ref()],
+        bump
+    )]
+    pub exemption: Option<Account<'info, Exemption>>,
+
     pub token_program: Program<'info, Token>,
 }
 
@@ -247,7 +580,165 @@ pub struct SetPaused<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateFees<'info> {
+pub struct QueueFeeUpdate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingFeeUpdate::INIT_SPACE,
+        seeds = [b"pending_fee_update"],
+        bump
+    )]
+    pub pending_fee_update: Account<'info, PendingFeeUpdate>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFeeUpdate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_fee_update"],
+        bump
+    )]
+    pub pending_fee_update: Account<'info, PendingFeeUpdate>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[cfg(feature = "classic-spl")]
+#[derive(Accounts)]
+pub struct ExecuteFeeUpdate<'info> {
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_fee_update"],
+        bump,
+        has_one = queued_by
+    )]
+    pub pending_fee_update: Account<'info, PendingFeeUpdate>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    /// CHECK: Gets the pending update's rent back
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub authority: Signer<'info>,
+}
+
+#[cfg(feature = "classic-spl")]
+#[derive(Accounts)]
+pub struct AddExemption<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Exemption::INIT_SPACE,
+        seeds = [b"exemption", token_account.key().as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(token::mint = token_data.mint)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(feature = "classic-spl")]
+#[derive(Accounts)]
+pub struct RemoveExemption<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"exemption", exemption.token_account.as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump,
+        constraint = token_data.pending_authority == new_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
     #[account(
         mut,
         seeds = [b"token_data"],
@@ -275,6 +766,168 @@ pub struct TokenData {
     pub burn_rate: u16,        // In basis points (500 = 5%)
     pub treasury_rate: u16,     // In basis points (500 = 5%)
     pub max_wallet_percent: u16, // In basis points (100 = 1%)
+    pub mint: Pubkey,
+    pub pending_authority: Pubkey, // Default pubkey when no transfer is pending
+    pub timelock_delay: i64,     // Seconds between queueing and executing a fee change
+    pub previous_burn_rate: u16,     // Token-2022: rates withheld until `fee_activation_epoch`
+    pub previous_treasury_rate: u16,
+    pub fee_activation_epoch: u64,   // Token-2022: epoch the mint's transfer fee switches to the current rates
+    pub pending_timelock_delay: i64, // Shorter delay waiting out the current one; 0 when none
+    pub timelock_delay_eta: i64,     // When `pending_timelock_delay` takes over
+}
+
+impl TokenData {
+    /// Burn and treasury rates of the fee withheld during `epoch`. Token-2022
+    /// only applies a new transfer fee two epochs after it is set.
+    pub fn fee_rates_at(&self, epoch: u64) -> (u16, u16) {
+        if epoch >= self.fee_activation_epoch {
+            (self.burn_rate, self.treasury_rate)
+        } else {
+            (self.previous_burn_rate, self.previous_treasury_rate)
+        }
+    }
+
+    /// Delay for changes queued at `now`: a pending shorter delay takes over
+    /// once the delay it replaces has passed
+    pub fn timelock_delay_at(&self, now: i64) -> i64 {
+        if self.pending_timelock_delay > 0 && now >= self.timelock_delay_eta {
+            self.pending_timelock_delay
+        } else {
+            self.timelock_delay
+        }
+    }
+
+    /// Change the delay for changes queued from `now` on and return when it
+    /// applies. A longer delay applies right away; a shorter one only once the
+    /// current delay has passed, so holders always get the notice they were
+    /// promised.
+    pub fn set_timelock_delay(&mut self, timelock_delay: i64, now: i64) -> i64 {
+        let current = self.timelock_delay_at(now);
+        self.timelock_delay = current.max(timelock_delay);
+        if timelock_delay >= current {
+            self.pending_timelock_delay = 0;
+            self.timelock_delay_eta = now;
+        } else {
+            self.pending_timelock_delay = timelock_delay;
+            self.timelock_delay_eta = now + current;
+        }
+        self.timelock_delay_eta
+    }
+}
+
+/// Fee change waiting for its timelock
+#[account]
+#[derive(InitSpace)]
+pub struct PendingFeeUpdate {
+    pub burn_rate: u16,
+    pub treasury_rate: u16,
+    pub eta: i64,          // Earliest execution time
+    pub queued_by: Pubkey, // Refunded the rent on execution
+}
+
+/// Allowlist entry for treasury, staking vault and liquidity pool token accounts
+#[account]
+#[derive(InitSpace)]
+pub struct Exemption {
+    pub token_account: Pubkey,
+    pub fee_exempt: bool,  // No burn or treasury fee when receiving
+    pub cap_exempt: bool,  // May hold more than max_wallet_percent
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct TokenInitialized {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub decimals: u8,
+    pub total_supply: u64,
+}
+
+#[event]
+pub struct TokensMinted {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub circulating_supply: u64,
+}
+
+#[event]
+pub struct TokensTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub recipient_amount: u64,
+    pub treasury_amount: u64,
+    pub burn_amount: u64,
+}
+
+#[event]
+pub struct FeeUpdateQueued {
+    pub burn_rate: u16,
+    pub treasury_rate: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct FeeUpdateCancelled {
+    pub burn_rate: u16,
+    pub treasury_rate: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_timelock_delay: i64,
+    pub new_timelock_delay: i64,
+    pub effective_at: i64, // Later than now when the delay is shortened
+}
+
+#[event]
+pub struct ExemptionAdded {
+    pub token_account: Pubkey,
+    pub fee_exempt: bool,
+    pub cap_exempt: bool,
+}
+
+#[event]
+pub struct ExemptionRemoved {
+    pub token_account: Pubkey,
+}
+
+#[event]
+pub struct WithheldFeesWithdrawn {
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub treasury_amount: u64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub was_paused: bool,
+    pub is_paused: bool,
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub old_burn_rate: u16,
+    pub new_burn_rate: u16,
+    pub old_treasury_rate: u16,
+    pub new_treasury_rate: u16,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
 }
 
 // ============================================================================
@@ -294,4 +947,16 @@ pub enum ErrorCode {
 
     #[msg("Total fees cannot exceed 20%")]
     FeesTooHigh,
+
+    #[msg("Treasury token account is not owned by the treasury")]
+    InvalidTreasuryAccount,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Timelock has not expired yet")]
+    TimelockNotExpired,
+
+    #[msg("Timelock delay must be between 1 and 30 days")]
+    InvalidTimelockDelay,
 }
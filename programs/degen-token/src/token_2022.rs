@@ -0,0 +1,583 @@
+//! Token-2022 variant of the $DEGEN program.
+//!
+//! The mint is created with the TransferFee extension, so the burn + treasury
+//! fee is withheld on every transfer instead of only inside `transfer_with_fees`,
+//! and with a TransferHook pointing at the anti-whale hook program. Withheld fees
+//! are harvested to the mint (permissionless, directly on Token-2022) and then
+//! split into burn and treasury by `withdraw_withheld`.
+
+use crate::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_fee, transfer_hook, ExtensionType};
+use anchor_spl::token_2022::spl_token_2022::{self, state::Mint as MintState};
+use anchor_spl::token_2022::{self, Burn, InitializeMint2, MintTo, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+pub(crate) fn initialize(ctx: Context<Initialize>, decimals: u8) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+    token_data.authority = ctx.accounts.authority.key();
+    token_data.mint = ctx.accounts.mint.key();
+    token_data.total_supply = 1_000_000_000 * 10u64.pow(decimals as u32); // 1B tokens
+    token_data.circulating_supply = 0;
+    token_data.decimals = decimals;
+    token_data.is_paused = false;
+    token_data.burn_rate = 500; // 5% (in basis points: 500/10000)
+    token_data.treasury_rate = 500; // 5%
+    token_data.treasury = ctx.accounts.treasury.key();
+    token_data.max_wallet_percent = 100; // 1% (in basis points: 100/10000)
+    token_data.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+
+    let token_data_key = token_data.key();
+    let mint_key = ctx.accounts.mint.key();
+    let mint_info = ctx.accounts.mint.to_account_info();
+
+    // Allocate the mint with room for both extensions
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&[
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::TransferHook,
+    ])?;
+    system_program::create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: mint_info.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &token_2022::ID,
+    )?;
+
+    // Extensions have to be initialized before the mint itself. The token data
+    // PDA controls the fee config and withheld fees, so only this program can
+    // change or collect them.
+    invoke(
+        &transfer_fee::instruction::initialize_transfer_fee_config(
+            &token_2022::ID,
+            &mint_key,
+            Some(&token_data_key),
+            Some(&token_data_key),
+            token_data.burn_rate + token_data.treasury_rate,
+            u64::MAX,
+        )?,
+        &[ctx.accounts.mint.to_account_info()],
+    )?;
+    invoke(
+        &transfer_hook::instruction::initialize(
+            &token_2022::ID,
+            &mint_key,
+            Some(token_data_key),
+            Some(ctx.accounts.transfer_hook_program.key()),
+        )?,
+        &[ctx.accounts.mint.to_account_info()],
+    )?;
+
+    // Mint authority is the token data PDA so the supply cap can't be bypassed
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeMint2 { mint: mint_info },
+        ),
+        decimals,
+        &token_data_key,
+        None,
+    )?;
+
+    emit!(TokenInitialized {
+        authority: token_data.authority,
+        mint: mint_key,
+        treasury: token_data.treasury,
+        decimals,
+        total_supply: token_data.total_supply,
+    });
+
+    msg!("$DEGEN Token-2022 initialized with {} decimals", decimals);
+    msg!("Total supply: {} tokens", token_data.total_supply);
+
+    Ok(())
+}
+
+pub(crate) fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+
+    require!(!token_data.is_paused, ErrorCode::ProgramPaused);
+
+    // Check total supply limit
+    require!(
+        token_data.circulating_supply + amount <= token_data.total_supply,
+        ErrorCode::ExceedsMaxSupply
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"token_data", &[ctx.bumps.token_data]]];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: token_data.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    token_2022::mint_to(cpi_ctx, amount)?;
+
+    token_data.circulating_supply += amount;
+
+    emit!(TokensMinted {
+        destination: ctx.accounts.destination.key(),
+        amount,
+        circulating_supply: token_data.circulating_supply,
+    });
+
+    msg!("Minted {} tokens. New circulating supply: {}", amount, token_data.circulating_supply);
+
+    Ok(())
+}
+
+pub(crate) fn withdraw_withheld<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawWithheld<'info>>) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"token_data", &[ctx.bumps.token_data]]];
+    let token_data_info = ctx.accounts.token_data.to_account_info();
+
+    // Move everything withheld at the mint into the fee vault
+    invoke_signed(
+        &transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+            &token_2022::ID,
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.fee_vault.key(),
+            &token_data_info.key(),
+            &[],
+        )?,
+        &[
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.fee_vault.to_account_info(),
+            token_data_info.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    ctx.accounts.fee_vault.reload()?;
+    let amount = ctx.accounts.fee_vault.amount;
+    let (burn_rate, treasury_rate) = ctx.accounts.token_data.fee_rates_at(Clock::get()?.epoch);
+    let (burn_amount, treasury_amount) = split_fees(amount, burn_rate, treasury_rate);
+
+    if burn_amount > 0 {
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.fee_vault.to_account_info(),
+            authority: token_data_info.clone(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_2022::burn(cpi_ctx, burn_amount)?;
+    }
+
+    // This is a regular Token-2022 transfer: the fee is withheld again on the
+    // treasury account (collected next time) and the transfer hook runs, so its
+    // extra accounts must be passed as remaining accounts.
+    if treasury_amount > 0 {
+        spl_token_2022::onchain::invoke_transfer_checked(
+            &token_2022::ID,
+            ctx.accounts.fee_vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.treasury_account.to_account_info(),
+            token_data_info,
+            ctx.remaining_accounts,
+            treasury_amount,
+            ctx.accounts.mint.decimals,
+            signer_seeds,
+        )?;
+    }
+
+    emit!(WithheldFeesWithdrawn {
+        amount,
+        burn_amount,
+        treasury_amount,
+    });
+
+    msg!(
+        "Withheld fees: {} collected, {} to treasury, {} burned",
+        amount,
+        treasury_amount,
+        burn_amount
+    );
+
+    Ok(())
+}
+
+pub(crate) fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+    let was_paused = token_data.is_paused;
+    token_data.is_paused = paused;
+
+    emit!(PauseUpdated {
+        was_paused,
+        is_paused: paused,
+    });
+
+    msg!("Token paused status set to: {}", paused);
+
+    Ok(())
+}
+
+pub(crate) fn queue_fee_update(ctx: Context<QueueFeeUpdate>, burn_rate: u16, treasury_rate: u16) -> Result<()> {
+    require!(burn_rate + treasury_rate <= 2000, ErrorCode::FeesTooHigh); // Max 20% total fees
+
+    let now = Clock::get()?.unix_timestamp;
+    let eta = now + ctx.accounts.token_data.timelock_delay_at(now);
+    let pending_fee_update = &mut ctx.accounts.pending_fee_update;
+    pending_fee_update.burn_rate = burn_rate;
+    pending_fee_update.treasury_rate = treasury_rate;
+    pending_fee_update.eta = eta;
+    pending_fee_update.queued_by = ctx.accounts.authority.key();
+
+    emit!(FeeUpdateQueued {
+        burn_rate,
+        treasury_rate,
+        eta,
+    });
+
+    msg!(
+        "Fee update queued - Burn: {}%, Treasury: {}%, executable at {}",
+        burn_rate / 100,
+        treasury_rate / 100,
+        eta
+    );
+
+    Ok(())
+}
+
+pub(crate) fn cancel_fee_update(ctx: Context<CancelFeeUpdate>) -> Result<()> {
+    let pending_fee_update = &ctx.accounts.pending_fee_update;
+
+    emit!(FeeUpdateCancelled {
+        burn_rate: pending_fee_update.burn_rate,
+        treasury_rate: pending_fee_update.treasury_rate,
+        eta: pending_fee_update.eta,
+    });
+
+    msg!("Fee update cancelled");
+
+    Ok(())
+}
+
+pub(crate) fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+    require!(
+        (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+        ErrorCode::InvalidTimelockDelay
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let token_data = &mut ctx.accounts.token_data;
+    let old_timelock_delay = token_data.timelock_delay_at(now);
+    let effective_at = token_data.set_timelock_delay(timelock_delay, now);
+
+    emit!(TimelockDelayUpdated {
+        old_timelock_delay,
+        new_timelock_delay: timelock_delay,
+        effective_at,
+    });
+
+    msg!("Timelock delay set to {} seconds from {}", timelock_delay, effective_at);
+
+    Ok(())
+}
+
+pub(crate) fn execute_fee_update(ctx: Context<ExecuteFeeUpdate>) -> Result<()> {
+    let pending_fee_update = &ctx.accounts.pending_fee_update;
+    require!(
+        Clock::get()?.unix_timestamp >= pending_fee_update.eta,
+        ErrorCode::TimelockNotExpired
+    );
+
+    let token_data = &mut ctx.accounts.token_data;
+    let old_burn_rate = token_data.burn_rate;
+    let old_treasury_rate = token_data.treasury_rate;
+
+    // Token-2022 applies a new transfer fee two epochs after it is set, so
+    // fees withheld until then are split at the rates being replaced. If the
+    // last change hasn't applied yet, the mint keeps withholding the fee
+    // before it. Fees withheld before the switch but withdrawn after it are
+    // split at the new rates; withdraw them in the epoch before to avoid it.
+    let epoch = Clock::get()?.epoch;
+    if epoch >= token_data.fee_activation_epoch {
+        token_data.previous_burn_rate = old_burn_rate;
+        token_data.previous_treasury_rate = old_treasury_rate;
+    }
+    token_data.burn_rate = pending_fee_update.burn_rate;
+    token_data.treasury_rate = pending_fee_update.treasury_rate;
+    token_data.fee_activation_epoch = epoch + 2;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"token_data", &[ctx.bumps.token_data]]];
+    invoke_signed(
+        &transfer_fee::instruction::set_transfer_fee(
+            &token_2022::ID,
+            &ctx.accounts.mint.key(),
+            &token_data.key(),
+            &[],
+            token_data.burn_rate + token_data.treasury_rate,
+            u64::MAX,
+        )?,
+        &[
+            ctx.accounts.mint.to_account_info(),
+            token_data.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    emit!(FeesUpdated {
+        old_burn_rate,
+        new_burn_rate: token_data.burn_rate,
+        old_treasury_rate,
+        new_treasury_rate: token_data.treasury_rate,
+    });
+
+    msg!(
+        "Fees updated - Burn: {}%, Treasury: {}%, withheld from epoch {}",
+        token_data.burn_rate / 100,
+        token_data.treasury_rate / 100,
+        token_data.fee_activation_epoch
+    );
+
+    Ok(())
+}
+
+pub(crate) fn add_exemption(ctx: Context<AddExemption>, fee_exempt: bool, cap_exempt: bool) -> Result<()> {
+    let exemption = &mut ctx.accounts.exemption;
+    exemption.token_account = ctx.accounts.token_account.key();
+    exemption.fee_exempt = fee_exempt;
+    exemption.cap_exempt = cap_exempt;
+
+    emit!(ExemptionAdded {
+        token_account: exemption.token_account,
+        fee_exempt,
+        cap_exempt,
+    });
+
+    msg!(
+        "Exemption added for {} - Fees: {}, Max wallet: {}",
+        exemption.token_account,
+        fee_exempt,
+        cap_exempt
+    );
+
+    Ok(())
+}
+
+pub(crate) fn remove_exemption(ctx: Context<RemoveExemption>) -> Result<()> {
+    emit!(ExemptionRemoved {
+        token_account: ctx.accounts.exemption.token_account,
+    });
+
+    msg!("Exemption removed for {}", ctx.accounts.exemption.token_account);
+
+    Ok(())
+}
+
+pub(crate) fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+    token_data.pending_authority = new_authority;
+
+    emit!(AuthorityProposed {
+        authority: token_data.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!("Authority transfer proposed to: {}", new_authority);
+
+    Ok(())
+}
+
+pub(crate) fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+    let old_authority = token_data.authority;
+    token_data.authority = token_data.pending_authority;
+    token_data.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        old_authority,
+        new_authority: token_data.authority,
+    });
+
+    msg!("Authority transferred to: {}", token_data.authority);
+
+    Ok(())
+}
+
+pub(crate) fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+    let token_data = &mut ctx.accounts.token_data;
+    let old_authority = token_data.authority;
+    token_data.authority = Pubkey::default();
+    token_data.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        old_authority,
+        new_authority: Pubkey::default(),
+    });
+
+    msg!("Authority renounced");
+
+    Ok(())
+}
+
+// ============================================================================
+// CONTEXTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenData::INIT_SPACE,
+        seeds = [b"token_data"],
+        bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// New mint keypair, created with its extensions in the handler
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// CHECK: Treasury wallet address
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Transfer hook program enforcing the max-wallet rule
+    #[account(executable)]
+    pub transfer_hook_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority,
+        has_one = mint
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheld<'info> {
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = mint
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = token_data
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = treasury_account.owner == token_data.treasury @ ErrorCode::InvalidTreasuryAccount
+    )]
+    pub treasury_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFeeUpdate<'info> {
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_fee_update"],
+        bump,
+        has_one = queued_by
+    )]
+    pub pending_fee_update: Account<'info, PendingFeeUpdate>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump,
+        has_one = mint
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    /// CHECK: Gets the pending update's rent back
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct AddExemption<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Exemption::INIT_SPACE,
+        seeds = [b"exemption", token_account.key().as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(token::mint = token_data.mint)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveExemption<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"exemption", exemption.token_account.as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        has_one = authority
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,201 @@
+// $DEGEN Token Event Tests
+// Emits events through the shared log-capturing syscall stub and decodes them
+// back from the "Program data:" lines, the same way the indexer does.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Event};
+use degen_token::{
+    AuthorityProposed, AuthorityTransferred, ExemptionAdded, ExemptionRemoved, FeeUpdateCancelled, FeeUpdateQueued,
+    FeesUpdated, PauseUpdated, TimelockDelayUpdated, TokenInitialized, TokensMinted, TokensTransferred,
+    WithheldFeesWithdrawn,
+};
+use event_testing::{capture_logs, decode_event, event_discriminator};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_discriminators() {
+        assert_eq!(FeeUpdateQueued::DISCRIMINATOR, event_discriminator("FeeUpdateQueued"));
+        assert_eq!(FeeUpdateCancelled::DISCRIMINATOR, event_discriminator("FeeUpdateCancelled"));
+        assert_eq!(TimelockDelayUpdated::DISCRIMINATOR, event_discriminator("TimelockDelayUpdated"));
+        assert_eq!(AuthorityProposed::DISCRIMINATOR, event_discriminator("AuthorityProposed"));
+        assert_eq!(AuthorityTransferred::DISCRIMINATOR, event_discriminator("AuthorityTransferred"));
+        assert_eq!(TokenInitialized::DISCRIMINATOR, event_discriminator("TokenInitialized"));
+        assert_eq!(TokensMinted::DISCRIMINATOR, event_discriminator("TokensMinted"));
+        assert_eq!(TokensTransferred::DISCRIMINATOR, event_discriminator("TokensTransferred"));
+        assert_eq!(WithheldFeesWithdrawn::DISCRIMINATOR, event_discriminator("WithheldFeesWithdrawn"));
+        assert_eq!(PauseUpdated::DISCRIMINATOR, event_discriminator("PauseUpdated"));
+        assert_eq!(ExemptionAdded::DISCRIMINATOR, event_discriminator("ExemptionAdded"));
+        assert_eq!(ExemptionRemoved::DISCRIMINATOR, event_discriminator("ExemptionRemoved"));
+        assert_eq!(FeesUpdated::DISCRIMINATOR, event_discriminator("FeesUpdated"));
+    }
+
+    #[test]
+    fn test_tokens_minted_event() {
+        let destination = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(TokensMinted {
+                destination,
+                amount: 1_000,
+                circulating_supply: 5_000,
+            });
+        });
+
+        let event: TokensMinted = decode_event(&logs).expect("TokensMinted not found in logs");
+        assert_eq!(event.destination, destination);
+        assert_eq!(event.amount, 1_000);
+        assert_eq!(event.circulating_supply, 5_000);
+    }
+
+    #[test]
+    fn test_tokens_transferred_event() {
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(TokensTransferred {
+                from,
+                to,
+                amount: 1_000,
+                recipient_amount: 900,
+                treasury_amount: 50,
+                burn_amount: 50,
+            });
+        });
+
+        let event: TokensTransferred = decode_event(&logs).expect("TokensTransferred not found in logs");
+        assert_eq!(event.from, from);
+        assert_eq!(event.to, to);
+        assert_eq!(event.amount, 1_000);
+        assert_eq!(event.recipient_amount, 900);
+        assert_eq!(event.treasury_amount, 50);
+        assert_eq!(event.burn_amount, 50);
+
+        // 8 discriminator + 2 pubkeys + 4 x u64
+        assert_eq!(event.data().len(), 8 + 64 + 32);
+    }
+
+    #[test]
+    fn test_withheld_fees_withdrawn_event() {
+        let logs = capture_logs(|| {
+            emit!(WithheldFeesWithdrawn {
+                amount: 2_000,
+                burn_amount: 1_000,
+                treasury_amount: 1_000,
+            });
+        });
+
+        let event: WithheldFeesWithdrawn = decode_event(&logs).expect("WithheldFeesWithdrawn not found in logs");
+        assert_eq!(event.amount, 2_000);
+        assert_eq!(event.burn_amount, 1_000);
+        assert_eq!(event.treasury_amount, 1_000);
+    }
+
+    #[test]
+    fn test_admin_events() {
+        let logs = capture_logs(|| {
+            emit!(PauseUpdated {
+                was_paused: false,
+                is_paused: true,
+            });
+            emit!(FeesUpdated {
+                old_burn_rate: 500,
+                new_burn_rate: 300,
+                old_treasury_rate: 500,
+                new_treasury_rate: 200,
+            });
+        });
+
+        let pause: PauseUpdated = decode_event(&logs).expect("PauseUpdated not found in logs");
+        assert!(!pause.was_paused);
+        assert!(pause.is_paused);
+
+        let fees: FeesUpdated = decode_event(&logs).expect("FeesUpdated not found in logs");
+        assert_eq!((fees.old_burn_rate, fees.new_burn_rate), (500, 300));
+        assert_eq!((fees.old_treasury_rate, fees.new_treasury_rate), (500, 200));
+    }
+
+    #[test]
+    fn test_exemption_events() {
+        let token_account = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(ExemptionAdded {
+                token_account,
+                fee_exempt: true,
+                cap_exempt: true,
+            });
+            emit!(ExemptionRemoved { token_account });
+        });
+
+        let added: ExemptionAdded = decode_event(&logs).expect("ExemptionAdded not found in logs");
+        assert_eq!(added.token_account, token_account);
+        assert!(added.fee_exempt);
+        assert!(added.cap_exempt);
+
+        let removed: ExemptionRemoved = decode_event(&logs).expect("ExemptionRemoved not found in logs");
+        assert_eq!(removed.token_account, token_account);
+    }
+
+    #[test]
+    fn test_authority_events() {
+        let authority = Pubkey::new_unique();
+        let pending_authority = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(AuthorityProposed {
+                authority,
+                pending_authority,
+            });
+            emit!(AuthorityTransferred {
+                old_authority: authority,
+                new_authority: pending_authority,
+            });
+        });
+
+        let proposed: AuthorityProposed = decode_event(&logs).expect("AuthorityProposed not found in logs");
+        assert_eq!(proposed.authority, authority);
+        assert_eq!(proposed.pending_authority, pending_authority);
+
+        let transferred: AuthorityTransferred = decode_event(&logs).expect("AuthorityTransferred not found in logs");
+        assert_eq!(transferred.old_authority, authority);
+        assert_eq!(transferred.new_authority, pending_authority);
+    }
+
+    #[test]
+    fn test_timelock_events() {
+        let logs = capture_logs(|| {
+            emit!(FeeUpdateQueued {
+                burn_rate: 300,
+                treasury_rate: 200,
+                eta: 1_700_172_800,
+            });
+            emit!(FeeUpdateCancelled {
+                burn_rate: 300,
+                treasury_rate: 200,
+                eta: 1_700_172_800,
+            });
+            emit!(TimelockDelayUpdated {
+                old_timelock_delay: 48 * 3600,
+                new_timelock_delay: 72 * 3600,
+                effective_at: 1_700_000_000,
+            });
+        });
+
+        let queued: FeeUpdateQueued = decode_event(&logs).expect("FeeUpdateQueued not found in logs");
+        assert_eq!((queued.burn_rate, queued.treasury_rate), (300, 200));
+        assert_eq!(queued.eta, 1_700_172_800);
+
+        let cancelled: FeeUpdateCancelled = decode_event(&logs).expect("FeeUpdateCancelled not found in logs");
+        assert_eq!((cancelled.burn_rate, cancelled.treasury_rate), (300, 200));
+        assert_eq!(cancelled.eta, queued.eta);
+
+        let delay: TimelockDelayUpdated = decode_event(&logs).expect("TimelockDelayUpdated not found in logs");
+        assert_eq!(delay.old_timelock_delay, 48 * 3600);
+        assert_eq!(delay.new_timelock_delay, 72 * 3600);
+        assert_eq!(delay.effective_at, 1_700_000_000);
+    }
+}
@@ -0,0 +1,133 @@
+// $DEGEN Token Instruction Event Tests
+// Runs the program under solana-program-test and decodes the events its
+// instructions emit, the same way the indexer does.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use degen_token::{PauseUpdated, TokenInitialized, TokensMinted};
+use event_testing::{decode_events, program_logs, record_program_logs};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::Instruction,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::Transaction,
+};
+
+// Anchor 0.29's entrypoint ties the account slice to 'info, program-test doesn't
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    degen_token::entry(program_id, accounts, data)
+}
+
+fn token_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"token_data"], &degen_token::ID).0
+}
+
+async fn send(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn test_admin_instructions_emit_events() {
+    let program_test = ProgramTest::new("degen_token", degen_token::ID, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    record_program_logs();
+    let payer = context.payer.pubkey();
+    let treasury = Pubkey::new_unique();
+
+    // The payer is the token authority and the mint authority
+    let mint = Keypair::new();
+    let destination = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    send(
+        &mut context,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), &payer, None, 6).unwrap(),
+            system_instruction::create_account(
+                &payer,
+                &destination.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::id(), &destination.pubkey(), &mint.pubkey(), &payer).unwrap(),
+        ],
+        &[&mint, &destination],
+    )
+    .await
+    .unwrap();
+
+    let initialize_ix = Instruction {
+        program_id: degen_token::ID,
+        accounts: degen_token::accounts::Initialize {
+            token_data: token_data_address(),
+            authority: payer,
+            mint: mint.pubkey(),
+            treasury,
+            system_program: system_program::ID,
+            token_program: spl_token::id(),
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: degen_token::instruction::Initialize { decimals: 6 }.data(),
+    };
+    let mint_ix = Instruction {
+        program_id: degen_token::ID,
+        accounts: degen_token::accounts::MintTokens {
+            token_data: token_data_address(),
+            authority: payer,
+            mint: mint.pubkey(),
+            destination: destination.pubkey(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: degen_token::instruction::MintTokens { amount: 5_000_000 }.data(),
+    };
+    let pause_ix = Instruction {
+        program_id: degen_token::ID,
+        accounts: degen_token::accounts::SetPaused {
+            token_data: token_data_address(),
+            authority: payer,
+        }
+        .to_account_metas(None),
+        data: degen_token::instruction::SetPaused { paused: true }.data(),
+    };
+    send(&mut context, &[initialize_ix, mint_ix, pause_ix], &[]).await.unwrap();
+
+    // Other tests' programs record into the same logs, so match on this mint
+    let logs = program_logs();
+    let initialized: Vec<TokenInitialized> = decode_events::<TokenInitialized>(&logs)
+        .into_iter()
+        .filter(|event| event.mint == mint.pubkey())
+        .collect();
+    assert_eq!(initialized.len(), 1);
+    assert_eq!(initialized[0].authority, payer);
+    assert_eq!(initialized[0].treasury, treasury);
+    assert_eq!(initialized[0].decimals, 6);
+    assert_eq!(initialized[0].total_supply, 1_000_000_000 * 10u64.pow(6));
+
+    let minted: Vec<TokensMinted> = decode_events::<TokensMinted>(&logs)
+        .into_iter()
+        .filter(|event| event.destination == destination.pubkey())
+        .collect();
+    assert_eq!(minted.len(), 1);
+    assert_eq!(minted[0].amount, 5_000_000);
+    assert_eq!(minted[0].circulating_supply, 5_000_000);
+
+    let paused = decode_events::<PauseUpdated>(&logs);
+    assert!(paused.iter().any(|event| !event.was_paused && event.is_paused));
+}
@@ -2,6 +2,7 @@
 // Tests instruction serialization, account validation, and access control
 
 use anchor_lang::prelude::*;
+use degen_token::{split_fees, Exemption, TokenData};
 
 #[cfg(test)]
 mod tests {
@@ -138,4 +139,113 @@ mod tests {
         
         assert_eq!(one_token, 1_000_000);
     }
+
+    #[test]
+    fn test_withheld_fee_split() {
+        // Default 5% burn / 5% treasury splits withheld fees evenly
+        assert_eq!(split_fees(1_000, 500, 500), (500, 500));
+
+        // 3% burn / 1% treasury: three quarters burned
+        assert_eq!(split_fees(1_000, 300, 100), (750, 250));
+    }
+
+    #[test]
+    fn test_withheld_fee_split_rounding() {
+        // Rounding dust goes to the treasury, nothing is lost
+        let (burn, treasury) = split_fees(1_001, 500, 500);
+        assert_eq!(burn, 500);
+        assert_eq!(burn + treasury, 1_001);
+
+        // No fee rates configured: everything to treasury
+        assert_eq!(split_fees(42, 0, 0), (0, 42));
+        assert_eq!(split_fees(u64::MAX, 2000, 0), (u64::MAX, 0));
+    }
+
+    /// Token state with the default 5% / 5% fees and timelock
+    fn token_data() -> TokenData {
+        TokenData {
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            total_supply: 1_000_000_000,
+            circulating_supply: 0,
+            decimals: 6,
+            is_paused: false,
+            burn_rate: 500,
+            treasury_rate: 500,
+            max_wallet_percent: 100,
+            mint: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            timelock_delay: degen_token::DEFAULT_TIMELOCK_DELAY,
+            previous_burn_rate: 0,
+            previous_treasury_rate: 0,
+            fee_activation_epoch: 0,
+            pending_timelock_delay: 0,
+            timelock_delay_eta: 0,
+        }
+    }
+
+    #[test]
+    fn test_fee_rates_follow_transfer_fee_activation() {
+        // 5% / 5% changed to 3% / 1% in epoch 10: Token-2022 withholds the new
+        // fee from epoch 12
+        let mut token_data = token_data();
+        token_data.burn_rate = 300;
+        token_data.treasury_rate = 100;
+        token_data.previous_burn_rate = 500;
+        token_data.previous_treasury_rate = 500;
+        token_data.fee_activation_epoch = 12;
+
+        assert_eq!(token_data.fee_rates_at(10), (500, 500));
+        assert_eq!(token_data.fee_rates_at(11), (500, 500));
+        assert_eq!(token_data.fee_rates_at(12), (300, 100));
+        assert_eq!(token_data.fee_rates_at(40), (300, 100));
+    }
+
+    #[test]
+    fn test_shorter_timelock_delay_waits_out_current_one() {
+        let day = 24 * 3600;
+        let mut token_data = token_data();
+
+        // Dropping from 48h to 24h: fee changes queued in the next 48h still wait 48h
+        assert_eq!(token_data.set_timelock_delay(day, 1_000), 1_000 + 2 * day);
+        assert_eq!(token_data.timelock_delay_at(1_000), 2 * day);
+        assert_eq!(token_data.timelock_delay_at(1_000 + 2 * day - 1), 2 * day);
+        assert_eq!(token_data.timelock_delay_at(1_000 + 2 * day), day);
+
+        // A longer delay applies right away and drops the pending shorter one
+        assert_eq!(token_data.set_timelock_delay(3 * day, 2_000), 2_000);
+        assert_eq!(token_data.timelock_delay_at(2_000), 3 * day);
+        assert_eq!(token_data.timelock_delay_at(2_000 + 10 * day), 3 * day);
+    }
+
+    #[test]
+    fn test_exemption_account_layout() {
+        // token_account + two flags
+        assert_eq!(Exemption::INIT_SPACE, 32 + 1 + 1);
+
+        let exemption = Exemption {
+            token_account: Pubkey::new_unique(),
+            fee_exempt: false,
+            cap_exempt: true,
+        };
+        let mut data = Vec::new();
+        exemption.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Exemption::INIT_SPACE);
+
+        let decoded = Exemption::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.token_account, exemption.token_account);
+        assert!(!decoded.fee_exempt);
+        assert!(decoded.cap_exempt);
+    }
+
+    #[test]
+    fn test_exemption_address_per_token_account() {
+        // One registry entry per destination token account
+        let vault = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let (vault_entry, _) = Pubkey::find_program_address(&[b"exemption", vault.as_ref()], &degen_token::ID);
+        let (treasury_entry, _) = Pubkey::find_program_address(&[b"exemption", treasury.as_ref()], &degen_token::ID);
+
+        assert_ne!(vault_entry, treasury_entry);
+    }
 }
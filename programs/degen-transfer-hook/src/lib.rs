@@ -0,0 +1,220 @@
+/**
+ * $DEGEN Transfer Hook Program
+ *
+ * Token-2022 calls this program on every transfer of the $DEGEN mint, so the
+ * anti-whale rule holds for wallet-to-wallet and DEX transfers too, not only
+ * for the ones routed through `transfer_with_fees`.
+ *
+ * Features:
+ * - Rejects transfers leaving the destination above `max_wallet_percent` of supply
+ * - Reads the limit live from the degen-token `TokenData` PDA
+ * - Rejects every transfer while the token is paused
+ * - Destinations marked `cap_exempt` in degen-token's exemption registry
+ *   (treasury, staking vault, pools) may go above the limit
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use degen_token::{Exemption, TokenData};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+declare_id!("DegenHook1111111111111111111111111111111111");
+
+/// Accounts the token program appends to every `Execute` call
+pub const EXTRA_ACCOUNT_COUNT: usize = 3;
+
+#[program]
+pub mod degen_transfer_hook {
+    use super::*;
+
+    /// Create the extra account metas list Token-2022 resolves on each transfer
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas(&ctx.accounts.token_data.key())?,
+        )?;
+
+        emit!(ExtraAccountMetaListInitialized {
+            mint: ctx.accounts.mint.key(),
+            extra_account_meta_list: ctx.accounts.extra_account_meta_list.key(),
+        });
+
+        msg!("Transfer hook initialized for mint {}", ctx.accounts.mint.key());
+
+        Ok(())
+    }
+
+    /// Enforce the pause and the max wallet limit after a transfer (called by Token-2022)
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        let token_data = &ctx.accounts.token_data;
+        require!(!token_data.is_paused, ErrorCode::TokenPaused);
+
+        if is_cap_exempt(&ctx.accounts.exemption) {
+            return Ok(());
+        }
+
+        let max_wallet_amount = max_wallet_amount(token_data.total_supply, token_data.max_wallet_percent);
+
+        // The token program has already moved the tokens, so this is the new balance
+        require!(
+            ctx.accounts.destination_token.amount <= max_wallet_amount,
+            ErrorCode::ExceedsMaxWalletSize
+        );
+
+        Ok(())
+    }
+
+    /// Route the SPL transfer hook interface `Execute` instruction, which uses
+    /// its own discriminator, to `transfer_hook`
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+// ============================================================================
+// CONTEXTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Extra account metas PDA, written in the instruction
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(EXTRA_ACCOUNT_COUNT)?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        seeds::program = degen_token::ID,
+        has_one = authority,
+        has_one = mint
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account order is fixed by the transfer hook interface: source, mint,
+/// destination, owner, extra metas list, then the extra accounts in list order
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, already validated by the token program
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Extra account metas PDA
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump,
+        seeds::program = degen_token::ID,
+        has_one = mint
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    /// CHECK: degen-token program, which owns the exemption registry
+    #[account(address = degen_token::ID)]
+    pub degen_token_program: AccountInfo<'info>,
+
+    /// CHECK: degen-token exemption PDA of the destination, only exempt if it exists
+    #[account(
+        seeds = [b"exemption", destination_token.key().as_ref()],
+        bump,
+        seeds::program = degen_token::ID
+    )]
+    pub exemption: AccountInfo<'info>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Extra accounts appended to `Execute`: the `TokenData` PDA (index 5), the
+/// degen-token program (index 6) and the destination's exemption PDA under it
+/// (index 7, derived from account index 2)
+pub fn extra_account_metas(token_data: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(token_data, false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&degen_token::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            6,
+            &[
+                Seed::Literal {
+                    bytes: b"exemption".to_vec(),
+                },
+                Seed::AccountKey { index: 2 },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// Largest balance a non-exempt wallet may hold
+pub fn max_wallet_amount(total_supply: u64, max_wallet_percent: u16) -> u64 {
+    (total_supply as u128 * max_wallet_percent as u128 / 10000) as u64
+}
+
+/// Closed exemptions are handed back to the system program, so only a live
+/// registry entry owned by degen-token with `cap_exempt` set counts
+fn is_cap_exempt(exemption: &AccountInfo) -> bool {
+    if exemption.owner != &degen_token::ID || exemption.data_is_empty() {
+        return false;
+    }
+    let Ok(data) = exemption.try_borrow_data() else {
+        return false;
+    };
+    Exemption::try_deserialize(&mut &data[..]).is_ok_and(|exemption| exemption.cap_exempt)
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct ExtraAccountMetaListInitialized {
+    pub mint: Pubkey,
+    pub extra_account_meta_list: Pubkey,
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Exceeds maximum wallet size (anti-whale protection)")]
+    ExceedsMaxWalletSize,
+
+    #[msg("Token is paused")]
+    TokenPaused,
+}
@@ -0,0 +1,297 @@
+// Transfer Hook Program Tests
+// Runs real Token-2022 transfers through the hook with solana-program-test
+
+use anchor_lang::prelude::*;
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_hook, ExtensionType},
+    state::Mint as MintState,
+};
+use degen_token::{Exemption, TokenData};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::{AccountMeta, Instruction},
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address_with_program_id, instruction::create_associated_token_account};
+
+const TOTAL_SUPPLY: u64 = 1_000_000;
+const MAX_WALLET_PERCENT: u16 = 100; // 1% -> 10_000 tokens
+
+// Anchor 0.29's entrypoint ties the account slice to 'info, program-test doesn't
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    degen_transfer_hook::entry(program_id, accounts, data)
+}
+
+struct Fixture {
+    context: ProgramTestContext,
+    authority: Keypair,
+    mint: Pubkey,
+    holder: Keypair,
+    source: Pubkey,
+}
+
+fn token_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"token_data"], &degen_token::ID).0
+}
+
+fn extra_account_meta_list_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"extra-account-metas", mint.as_ref()], &degen_transfer_hook::ID).0
+}
+
+fn exemption_address(token_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"exemption", token_account.as_ref()], &degen_token::ID).0
+}
+
+/// degen-token's `TokenData` PDA for `mint`
+fn token_data_account(authority: &Pubkey, mint: &Pubkey, is_paused: bool) -> Account {
+    let token_data = TokenData {
+        authority: *authority,
+        treasury: *authority,
+        total_supply: TOTAL_SUPPLY,
+        circulating_supply: 0,
+        decimals: 0,
+        is_paused,
+        burn_rate: 0,
+        treasury_rate: 0,
+        max_wallet_percent: MAX_WALLET_PERCENT,
+        mint: *mint,
+        pending_authority: Pubkey::default(),
+        timelock_delay: degen_token::DEFAULT_TIMELOCK_DELAY,
+        previous_burn_rate: 0,
+        previous_treasury_rate: 0,
+        fee_activation_epoch: 0,
+        pending_timelock_delay: 0,
+        timelock_delay_eta: 0,
+    };
+    let mut data = Vec::new();
+    token_data.try_serialize(&mut data).unwrap();
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: degen_token::ID,
+        ..Account::default()
+    }
+}
+
+fn token_account_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::id())
+}
+
+async fn send(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Hooked mint, initialized metas list and a holder with 100_000 tokens
+async fn setup() -> Fixture {
+    let authority = Keypair::new();
+    let mint = Keypair::new();
+    let holder = Keypair::new();
+
+    let mut program_test = ProgramTest::new("degen_transfer_hook", degen_transfer_hook::ID, processor!(process_instruction));
+
+    // degen-token is only read by the hook, so its accounts are injected directly
+    program_test.add_account(token_data_address(), token_data_account(&authority.pubkey(), &mint.pubkey(), false));
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    // Token-2022 mint with the transfer hook pointing at this program
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&[ExtensionType::TransferHook]).unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    send(
+        &mut context,
+        &[
+            system_instruction::create_account(&payer, &mint.pubkey(), rent.minimum_balance(space), space as u64, &spl_token_2022::id()),
+            transfer_hook::instruction::initialize(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                Some(authority.pubkey()),
+                Some(degen_transfer_hook::ID),
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint2(&spl_token_2022::id(), &mint.pubkey(), &authority.pubkey(), None, 0).unwrap(),
+        ],
+        &[&mint],
+    )
+    .await
+    .unwrap();
+
+    let init_ix = Instruction {
+        program_id: degen_transfer_hook::ID,
+        accounts: degen_transfer_hook::accounts::InitializeExtraAccountMetaList {
+            extra_account_meta_list: extra_account_meta_list_address(&mint.pubkey()),
+            mint: mint.pubkey(),
+            token_data: token_data_address(),
+            authority: authority.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: degen_transfer_hook::instruction::InitializeExtraAccountMetaList {}.data(),
+    };
+    send(&mut context, &[init_ix], &[&authority]).await.unwrap();
+
+    // Minting doesn't go through the hook
+    let source = token_account_address(&holder.pubkey(), &mint.pubkey());
+    send(
+        &mut context,
+        &[
+            create_associated_token_account(&payer, &holder.pubkey(), &mint.pubkey(), &spl_token_2022::id()),
+            spl_token_2022::instruction::mint_to(&spl_token_2022::id(), &mint.pubkey(), &source, &authority.pubkey(), &[], 100_000).unwrap(),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    Fixture {
+        context,
+        authority,
+        mint: mint.pubkey(),
+        holder,
+        source,
+    }
+}
+
+async fn create_token_account(fixture: &mut Fixture, owner: &Pubkey) -> Pubkey {
+    let payer = fixture.context.payer.pubkey();
+    let ix = create_associated_token_account(&payer, owner, &fixture.mint, &spl_token_2022::id());
+    send(&mut fixture.context, &[ix], &[]).await.unwrap();
+    token_account_address(owner, &fixture.mint)
+}
+
+async fn transfer(fixture: &mut Fixture, destination: &Pubkey, amount: u64) -> std::result::Result<(), BanksClientError> {
+    let mut ix = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::id(),
+        &fixture.source,
+        &fixture.mint,
+        destination,
+        &fixture.holder.pubkey(),
+        &[],
+        amount,
+        0,
+    )
+    .unwrap();
+    // Extra accounts the token program resolves from the metas list
+    ix.accounts.extend([
+        AccountMeta::new_readonly(extra_account_meta_list_address(&fixture.mint), false),
+        AccountMeta::new_readonly(token_data_address(), false),
+        AccountMeta::new_readonly(degen_token::ID, false),
+        AccountMeta::new_readonly(exemption_address(destination), false),
+        AccountMeta::new_readonly(degen_transfer_hook::ID, false),
+    ]);
+
+    let holder = fixture.holder.insecure_clone();
+    send(&mut fixture.context, &[ix], &[&holder]).await
+}
+
+/// Write a degen-token registry entry for `token_account`, as `add_exemption` would
+fn add_exemption(fixture: &mut Fixture, token_account: &Pubkey, cap_exempt: bool) {
+    let exemption = Exemption {
+        token_account: *token_account,
+        fee_exempt: true,
+        cap_exempt,
+    };
+    let mut data = Vec::new();
+    exemption.try_serialize(&mut data).unwrap();
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: degen_token::ID,
+        ..Account::default()
+    };
+    fixture.context.set_account(&exemption_address(token_account), &account.into());
+}
+
+/// Close the registry entry, as `remove_exemption` would
+fn remove_exemption(fixture: &mut Fixture, token_account: &Pubkey) {
+    fixture.context.set_account(&exemption_address(token_account), &AccountSharedData::default());
+}
+
+fn set_paused(fixture: &mut Fixture, is_paused: bool) {
+    let account = token_data_account(&fixture.authority.pubkey(), &fixture.mint, is_paused);
+    fixture.context.set_account(&token_data_address(), &account.into());
+}
+
+#[tokio::test]
+async fn test_transfer_under_cap_succeeds() {
+    let mut fixture = setup().await;
+    let wallet = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+
+    assert!(transfer(&mut fixture, &wallet, 5_000).await.is_ok());
+    // Landing exactly on the cap is still allowed
+    assert!(transfer(&mut fixture, &wallet, 5_000).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_transfer_over_cap_rejected() {
+    let mut fixture = setup().await;
+    let wallet = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+
+    assert!(transfer(&mut fixture, &wallet, 10_001).await.is_err());
+
+    // Splitting into smaller transfers doesn't get around the cap
+    assert!(transfer(&mut fixture, &wallet, 9_000).await.is_ok());
+    assert!(transfer(&mut fixture, &wallet, 2_000).await.is_err());
+}
+
+#[tokio::test]
+async fn test_exempt_destination_accepts_over_cap() {
+    let mut fixture = setup().await;
+    let vault = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+
+    add_exemption(&mut fixture, &vault, true);
+    assert!(transfer(&mut fixture, &vault, 50_000).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_removed_exemption_enforces_cap_again() {
+    let mut fixture = setup().await;
+    let vault = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+
+    add_exemption(&mut fixture, &vault, true);
+    assert!(transfer(&mut fixture, &vault, 20_000).await.is_ok());
+
+    remove_exemption(&mut fixture, &vault);
+    assert!(transfer(&mut fixture, &vault, 1).await.is_err());
+}
+
+#[tokio::test]
+async fn test_fee_only_exemption_enforces_cap() {
+    let mut fixture = setup().await;
+    let vault = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+
+    add_exemption(&mut fixture, &vault, false);
+    assert!(transfer(&mut fixture, &vault, 20_000).await.is_err());
+}
+
+#[tokio::test]
+async fn test_paused_token_rejects_transfers() {
+    let mut fixture = setup().await;
+    let wallet = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+    let vault = create_token_account(&mut fixture, &Pubkey::new_unique()).await;
+    add_exemption(&mut fixture, &vault, true);
+
+    set_paused(&mut fixture, true);
+    assert!(transfer(&mut fixture, &wallet, 1).await.is_err());
+    assert!(transfer(&mut fixture, &vault, 1).await.is_err());
+
+    set_paused(&mut fixture, false);
+    assert!(transfer(&mut fixture, &wallet, 1).await.is_ok());
+}
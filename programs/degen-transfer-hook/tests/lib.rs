@@ -0,0 +1,48 @@
+// Transfer Hook Program Tests
+// Tests the max wallet math and the extra account metas layout
+
+use anchor_lang::prelude::*;
+use degen_transfer_hook::{extra_account_metas, max_wallet_amount, EXTRA_ACCOUNT_COUNT};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_wallet_amount() {
+        // 1% of 1B tokens with 6 decimals
+        let total_supply = 1_000_000_000 * 10u64.pow(6);
+        assert_eq!(max_wallet_amount(total_supply, 100), 10_000_000 * 10u64.pow(6));
+
+        // Rounds down, never above the cap
+        assert_eq!(max_wallet_amount(999, 100), 9);
+        assert_eq!(max_wallet_amount(total_supply, 0), 0);
+    }
+
+    #[test]
+    fn test_max_wallet_amount_no_overflow() {
+        // Full-range supply times basis points doesn't fit in a u64
+        assert_eq!(max_wallet_amount(u64::MAX, 10000), u64::MAX);
+        assert_eq!(max_wallet_amount(u64::MAX, 100), u64::MAX / 100);
+    }
+
+    #[test]
+    fn test_extra_account_metas() {
+        let token_data = Pubkey::new_unique();
+        let metas = extra_account_metas(&token_data).unwrap();
+
+        assert_eq!(metas.len(), EXTRA_ACCOUNT_COUNT);
+
+        // Fixed address entries store the key itself; the exemption is a PDA
+        // of the program at index 6 (128 + program index)
+        assert_eq!(metas[0].discriminator, 0);
+        assert_eq!(metas[0].address_config, token_data.to_bytes());
+        assert_eq!(metas[1].discriminator, 0);
+        assert_eq!(metas[1].address_config, degen_token::ID.to_bytes());
+        assert_eq!(metas[2].discriminator, 128 + 6);
+        for meta in &metas {
+            assert!(!bool::from(meta.is_signer));
+            assert!(!bool::from(meta.is_writable));
+        }
+    }
+}
@@ -3,74 +3,153 @@
  *
  * Lock $DEGEN tokens to earn rewards and unlock premium features.
  *
- * Tiers:
+ * Tiers (defaults, configurable):
  * - STAKER: 10,000 $DEGEN (2x rewards)
  * - WHALE: 100,000 $DEGEN (5x rewards + profit sharing)
  *
  * Features:
- * - Variable APY based on lock duration
+ * - Variable APY based on lock duration, or a funded reward rate split by
+ *   weighted stake (amount x lock weight x tier multiplier)
  * - Tiered multipliers
- * - Early withdrawal penalty (20% to other stakers)
- * - Compound rewards
+ * - Lock options, tier thresholds and multipliers in a timelocked pool config;
+ *   positions keep the lock terms and tier multipliers they were opened with
+ * - Multiple positions per wallet, each with its own amount, lock and APY;
+ *   the tier comes from the sum of all open positions, and a tier change
+ *   re-weights every one of them
+ * - Early withdrawal penalty on the amount withdrawn, 20% at lock start
+ *   decaying linearly to 0 at unlock, paid to other stakers pro rata;
+ *   partial unstaking keeps the rest of the position
+ * - Compound rewards, or let a permissionless crank do it for a small bounty
+ * - Flexible positions with no lock: `request_unstake` starts a cooldown
+ *   earning nothing, `withdraw` returns the tokens once it is over
+ * - Reward vault solvency: accrued-but-unpaid rewards are tracked as pool
+ *   liabilities, a short vault pays what it holds and records the rest as
+ *   an IOU on the position, and `reward_runway` reports how long it lasts
  */
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("DegenStake1111111111111111111111111111111");
 
-// Lock durations in seconds
+// Default lock options: durations in seconds, 0 = flexible (cooldown instead of a lock)
+const LOCK_FLEXIBLE: i64 = 0;
 const LOCK_30_DAYS: i64 = 30 * 24 * 3600;
 const LOCK_90_DAYS: i64 = 90 * 24 * 3600;
 const LOCK_180_DAYS: i64 = 180 * 24 * 3600;
 const LOCK_365_DAYS: i64 = 365 * 24 * 3600;
 
 // APY rates (in basis points: 2000 = 20%)
+const APY_FLEXIBLE: u16 = 500;   // 5% APY
 const APY_30_DAYS: u16 = 2000;   // 20% APY
 const APY_90_DAYS: u16 = 4000;   // 40% APY
 const APY_180_DAYS: u16 = 8000;  // 80% APY
 const APY_365_DAYS: u16 = 15000; // 150% APY
 
-// Tier thresholds
-const STAKER_THRESHOLD: u64 = 10_000 * 10u64.pow(9); // 10k tokens (9 decimals)
-const WHALE_THRESHOLD: u64 = 100_000 * 10u64.pow(9); // 100k tokens
+// Lock weights for reward-rate mode (basis points), same ratios as the APYs
+const WEIGHT_FLEXIBLE: u64 = 5_000;  // 0.5x
+const WEIGHT_30_DAYS: u64 = 10_000;  // 1x
+const WEIGHT_90_DAYS: u64 = 20_000;  // 2x
+const WEIGHT_180_DAYS: u64 = 40_000; // 4x
+const WEIGHT_365_DAYS: u64 = 75_000; // 7.5x
+
+// Default tier thresholds in whole tokens, scaled by the mint's decimals
+const STAKER_THRESHOLD_TOKENS: u64 = 10_000;
+const WHALE_THRESHOLD_TOKENS: u64 = 100_000;
+
+// Default tier multipliers: None / Staker / Whale
+const TIER_MULTIPLIERS: [u64; 3] = [1, 2, 5];
+
+// Cooldown of flexible positions between `request_unstake` and `withdraw`
+const DEFAULT_COOLDOWN_PERIOD: i64 = 7 * 24 * 3600;
+pub const MAX_COOLDOWN_PERIOD: i64 = 30 * 24 * 3600;
+
+// Most lock options a pool can offer at once
+pub const MAX_LOCK_OPTIONS: usize = 8;
+
+// Most open positions per wallet, so a tier change can re-weight all of them
+// in one transaction
+pub const MAX_OPEN_POSITIONS: u32 = 16;
+
+// Timelock on pool config changes
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 48 * 3600;
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 3600;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 3600;
+
+// Early withdrawal penalty at lock start (basis points), decays linearly to 0
+pub const MAX_EARLY_PENALTY_BP: u64 = 2000; // 20%
+
+// Auto-compound crank: bounty (basis points of the rewards) and minimum interval
+pub const COMPOUND_BOUNTY_BP: u64 = 50; // 0.5%
+pub const COMPOUND_CRANK_INTERVAL: i64 = 24 * 3600;
+
+// Fixed-point scale of the penalty-per-share accumulator
+pub const PENALTY_PRECISION: u128 = 1_000_000_000_000;
+
+// Fixed APY rewards are pro-rated over a 365-day year
+const SECONDS_PER_YEAR: u128 = 365 * 86400;
+
+// Fixed-point scale of the reward-per-weight accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+// Smallest total weight the reward-rate emission is split over. Dust positions
+// staked on their own can't push the accumulator up faster than this allows.
+pub const REWARD_WEIGHT_FLOOR: u128 = 1_000;
 
 #[program]
 pub mod staking {
     use super::*;
 
     /// Initialize the staking pool (one-time)
-    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+    pub fn initialize_pool(ctx: Context<InitializePool>, reward_mode: RewardMode) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
         pool.authority = ctx.accounts.authority.key();
+        pool.reward_mode = reward_mode;
         pool.total_staked = 0;
         pool.total_stakers = 0;
+        pool.mint = ctx.accounts.mint.key();
+        pool.pool_token_account = ctx.accounts.pool_token_account.key();
         pool.reward_vault = ctx.accounts.reward_vault.key();
+        pool.timelock_delay = DEFAULT_TIMELOCK_DELAY;
 
-        msg!("Staking pool initialized");
+        let pool_config = PoolConfig::with_defaults(pool.key(), ctx.accounts.mint.decimals)
+            .ok_or(ErrorCode::InvalidPoolConfig)?;
+        ctx.accounts.pool_config.set_inner(pool_config);
+
+        emit!(PoolInitialized {
+            authority: pool.authority,
+            mint: pool.mint,
+            pool_token_account: pool.pool_token_account,
+            reward_vault: pool.reward_vault,
+            reward_mode,
+        });
+
+        msg!("Staking pool initialized - Reward mode: {:?}", reward_mode);
 
         Ok(())
     }
 
-    /// Stake tokens with a lock period
-    pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+    /// Open a new stake position with its own amount, lock and APY.
+    /// If it changes the owner's tier, their other open positions have to be
+    /// passed as writable remaining accounts.
+    pub fn open_position(ctx: Context<OpenPosition>, amount: u64, lock_duration: i64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(
-            lock_duration == LOCK_30_DAYS ||
-            lock_duration == LOCK_90_DAYS ||
-            lock_duration == LOCK_180_DAYS ||
-            lock_duration == LOCK_365_DAYS,
-            ErrorCode::InvalidLockDuration
-        );
+        let pool_config = &ctx.accounts.pool_config;
+        let lock_option = pool_config
+            .lock_option(lock_duration)
+            .ok_or(ErrorCode::InvalidLockDuration)?;
+        require!(has_stake_weight(amount, lock_option.weight), ErrorCode::PositionTooSmall);
 
         let stake_account = &mut ctx.accounts.stake_account;
+        let staker_summary = &mut ctx.accounts.staker_summary;
         let pool = &mut ctx.accounts.pool;
         let clock = Clock::get()?;
 
-        // If first stake, initialize
-        if stake_account.amount == 0 {
-            stake_account.owner = ctx.accounts.owner.key();
-            stake_account.start_time = clock.unix_timestamp;
+        require!(staker_summary.open_positions < MAX_OPEN_POSITIONS, ErrorCode::TooManyPositions);
+
+        // First open position of this wallet
+        if staker_summary.open_positions == 0 {
+            staker_summary.owner = ctx.accounts.owner.key();
             pool.total_stakers += 1;
         }
 
@@ -84,272 +163,1722 @@ pub mod staking {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, amount)?;
 
-        // Update stake account
-        stake_account.amount += amount;
+        // Tier comes from the sum of all open positions
+        let tier_before = staker_summary.tier.clone();
+        stake_account.position_index = staker_summary.add_position(amount, pool_config);
+
+        stake_account.owner = ctx.accounts.owner.key();
+        stake_account.amount = amount;
+        stake_account.start_time = clock.unix_timestamp;
         stake_account.lock_until = clock.unix_timestamp + lock_duration;
         stake_account.last_claim = clock.unix_timestamp;
+        stake_account.last_accrual = clock.unix_timestamp;
         stake_account.lock_duration = lock_duration;
-
-        // Determine tier
-        stake_account.tier = if stake_account.amount >= WHALE_THRESHOLD {
-            Tier::Whale
-        } else if stake_account.amount >= STAKER_THRESHOLD {
-            Tier::Staker
-        } else {
-            Tier::None
-        };
-
-        // Calculate APY
-        stake_account.apy_basis_points = match lock_duration {
-            LOCK_30_DAYS => APY_30_DAYS,
-            LOCK_90_DAYS => APY_90_DAYS,
-            LOCK_180_DAYS => APY_180_DAYS,
-            LOCK_365_DAYS => APY_365_DAYS,
-            _ => 0,
-        };
+        stake_account.status = PositionStatus::Active;
+        stake_account.reset_penalty_debt(pool.acc_penalty_per_share);
+
+        // Lock terms and tier multipliers are fixed for the life of the position
+        stake_account.apy_basis_points = lock_option.apy_basis_points;
+        stake_account.lock_weight = lock_option.weight;
+        stake_account.tier_multipliers = pool_config.tier_multipliers;
+
+        // Weight the position, after bringing the reward accumulator up to date
+        pool.update_rewards(clock.unix_timestamp)?;
+        let tier_multiplier = stake_account.tier_multiplier(&staker_summary.tier);
+        stake_account.weight = stake_weight(amount, stake_account.lock_weight, tier_multiplier);
+        pool.total_weight += stake_account.weight;
+        stake_account.reset_reward_debt(pool.acc_reward_per_weight);
+        stake_account.apy_rate = apy_rate(amount, stake_account.apy_basis_points, tier_multiplier);
+        pool.total_apy_rate += stake_account.apy_rate;
 
         // Update pool
         pool.total_staked += amount;
 
+        if staker_summary.tier != tier_before {
+            reweight_positions(
+                pool,
+                staker_summary,
+                &stake_account.key(),
+                stake_account.amount > 0,
+                ctx.remaining_accounts,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        emit!(Staked {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount,
+            total_amount: staker_summary.total_amount,
+            lock_duration,
+            lock_until: stake_account.lock_until,
+            apy_basis_points: stake_account.apy_basis_points,
+            tier: staker_summary.tier.clone(),
+            pool_total_staked: pool.total_staked,
+        });
+
         msg!(
-            "Staked {} tokens for {} days. Tier: {:?}, APY: {}%",
+            "Opened position #{}: {} tokens for {} days. Tier: {:?}, APY: {}%",
+            stake_account.position_index,
             amount,
             lock_duration / 86400,
-            stake_account.tier,
+            staker_summary.tier,
             stake_account.apy_basis_points / 100
         );
 
         Ok(())
     }
 
-    /// Claim rewards
+    /// Claim the rewards of one position
     pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         let stake_account = &mut ctx.accounts.stake_account;
+        let pool = &mut ctx.accounts.pool;
         let clock = Clock::get()?;
 
         let time_staked = clock.unix_timestamp - stake_account.last_claim;
         require!(time_staked > 0, ErrorCode::NoRewardsToClaim);
 
-        // Calculate rewards
-        let base_reward = calculate_rewards(
-            stake_account.amount,
-            stake_account.apy_basis_points,
-            time_staked,
-        );
-
-        // Apply tier multiplier
-        let multiplier = match stake_account.tier {
-            Tier::None => 1,
-            Tier::Staker => 2,
-            Tier::Whale => 5,
-        };
-
-        let total_reward = base_reward * multiplier;
+        let multiplier = stake_account.tier_multiplier(&ctx.accounts.staker_summary.tier);
+        let (total_reward, penalty_share) = settle_position(
+            pool,
+            stake_account,
+            ctx.accounts.reward_vault.amount,
+            clock.unix_timestamp,
+        )?;
 
         // Transfer rewards from vault
+        let pool_key = pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
         let cpi_accounts = Transfer {
             from: ctx.accounts.reward_vault.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.pool_authority.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
         token::transfer(cpi_ctx, total_reward)?;
 
-        // Update claim time
-        stake_account.last_claim = clock.unix_timestamp;
-        stake_account.total_claimed += total_reward;
+        if penalty_share > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, penalty_share)?;
+        }
+
+        emit!(RewardsClaimed {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount: total_reward,
+            multiplier,
+            time_staked,
+            total_claimed: stake_account.total_claimed,
+            penalty_share,
+            unpaid_rewards: stake_account.unpaid_rewards,
+        });
 
         msg!(
-            "Claimed {} tokens ({}x multiplier). Total claimed: {}",
+            "Claimed {} tokens ({}x multiplier) + {} penalty share from position #{}. Total claimed: {}, unpaid: {}",
             total_reward,
             multiplier,
-            stake_account.total_claimed
+            penalty_share,
+            stake_account.position_index,
+            stake_account.total_claimed,
+            stake_account.unpaid_rewards
+        );
+
+        // Last IOU of an unstaked position paid off
+        if ctx.accounts.stake_account.is_closable() {
+            ctx.accounts.stake_account.close(ctx.accounts.owner.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Restake a position's rewards and penalty share into the same position,
+    /// keeping its lock
+    /// Remaining accounts as for `open_position`.
+    pub fn compound(ctx: Context<Compound>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let staker_summary = &mut ctx.accounts.staker_summary;
+        let pool = &mut ctx.accounts.pool;
+        let pool_config = &ctx.accounts.pool_config;
+        let clock = Clock::get()?;
+
+        require!(stake_account.status == PositionStatus::Active, ErrorCode::PositionNotActive);
+        require!(clock.unix_timestamp > stake_account.last_claim, ErrorCode::NoRewardsToClaim);
+
+        let (rewards, penalty_share) =
+            settle_position(
+                pool,
+                stake_account,
+                ctx.accounts.reward_vault.amount,
+                clock.unix_timestamp,
+            )?;
+        require!(rewards + penalty_share > 0, ErrorCode::NoRewardsToClaim);
+
+        // Rewards move into the staking vault, the penalty share is already there
+        let pool_key = pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
+        if rewards > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.pool_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, rewards)?;
+        }
+
+        let compounded = rewards + penalty_share;
+        let tier_before = staker_summary.tier.clone();
+        add_to_position(pool, pool_config, staker_summary, stake_account, compounded, clock.unix_timestamp)?;
+        if staker_summary.tier != tier_before {
+            reweight_positions(
+                pool,
+                staker_summary,
+                &stake_account.key(),
+                stake_account.amount > 0,
+                ctx.remaining_accounts,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        emit!(Compounded {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount: compounded,
+            bounty: 0,
+            cranker: stake_account.owner,
+            position_amount: stake_account.amount,
+            tier: staker_summary.tier.clone(),
+        });
+
+        msg!(
+            "Compounded {} tokens into position #{}. Position: {}, Tier: {:?}",
+            compounded,
+            stake_account.position_index,
+            stake_account.amount,
+            staker_summary.tier
+        );
+
+        Ok(())
+    }
+
+    /// Let anyone compound this position for a bounty (owner only)
+    pub fn set_auto_compound(ctx: Context<SetAutoCompound>, enabled: bool) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.auto_compound = enabled;
+
+        emit!(AutoCompoundSet {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            enabled,
+        });
+
+        msg!("Auto-compound for position #{}: {}", stake_account.position_index, enabled);
+
+        Ok(())
+    }
+
+    /// Compound a position that opted into auto-compounding (permissionless).
+    /// The cranker is paid `COMPOUND_BOUNTY_BP` of the rewards.
+    /// Remaining accounts as for `open_position`.
+    pub fn crank_compound(ctx: Context<CrankCompound>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let staker_summary = &mut ctx.accounts.staker_summary;
+        let pool = &mut ctx.accounts.pool;
+        let pool_config = &ctx.accounts.pool_config;
+        let clock = Clock::get()?;
+
+        require!(stake_account.status == PositionStatus::Active, ErrorCode::PositionNotActive);
+        require!(stake_account.auto_compound, ErrorCode::AutoCompoundDisabled);
+        require!(
+            clock.unix_timestamp >= stake_account.last_claim + COMPOUND_CRANK_INTERVAL,
+            ErrorCode::CompoundTooSoon
+        );
+
+        let (rewards, penalty_share) =
+            settle_position(
+                pool,
+                stake_account,
+                ctx.accounts.reward_vault.amount,
+                clock.unix_timestamp,
+            )?;
+        require!(rewards + penalty_share > 0, ErrorCode::NoRewardsToClaim);
+
+        let bounty = compound_bounty(rewards);
+
+        let pool_key = pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
+        if rewards > bounty {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.pool_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, rewards - bounty)?;
+        }
+
+        if bounty > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.cranker_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, bounty)?;
+        }
+
+        // The bounty was paid out of the rewards, not to the owner
+        stake_account.total_claimed -= bounty;
+
+        let compounded = rewards - bounty + penalty_share;
+        let tier_before = staker_summary.tier.clone();
+        add_to_position(pool, pool_config, staker_summary, stake_account, compounded, clock.unix_timestamp)?;
+        if staker_summary.tier != tier_before {
+            reweight_positions(
+                pool,
+                staker_summary,
+                &stake_account.key(),
+                stake_account.amount > 0,
+                ctx.remaining_accounts,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        emit!(Compounded {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount: compounded,
+            bounty,
+            cranker: ctx.accounts.cranker.key(),
+            position_amount: stake_account.amount,
+            tier: staker_summary.tier.clone(),
+        });
+
+        msg!(
+            "Auto-compounded {} tokens into position #{} ({} bounty)",
+            compounded,
+            stake_account.position_index,
+            bounty
         );
 
         Ok(())
     }
 
-    /// Unstake tokens
-    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+    /// Close a position: pays back the principal (minus the early withdrawal
+    /// penalty) together with everything the position has earned
+    /// Remaining accounts as for `open_position`.
+    pub fn close_position(ctx: Context<Unstake>) -> Result<()> {
+        let amount = ctx.accounts.stake_account.amount;
+        unstake_from_position(ctx, amount)
+    }
+
+    /// Withdraw part of a position. Pending rewards are paid out first and
+    /// the penalty only applies to the amount withdrawn;
+    /// the position is closed once nothing is left in it.
+    /// Remaining accounts as for `open_position`.
+    pub fn unstake_partial(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        unstake_from_position(ctx, amount)
+    }
+
+    /// Start the cooldown of a flexible position: everything earned so far is
+    /// paid out and the tokens stop earning until they are withdrawn
+    /// Remaining accounts as for `open_position`.
+    pub fn request_unstake(ctx: Context<RequestUnstake>) -> Result<()> {
         let stake_account = &mut ctx.accounts.stake_account;
+        let staker_summary = &mut ctx.accounts.staker_summary;
         let pool = &mut ctx.accounts.pool;
+        let pool_config = &ctx.accounts.pool_config;
         let clock = Clock::get()?;
 
+        require!(stake_account.is_flexible(), ErrorCode::NotFlexiblePosition);
+        require!(stake_account.status == PositionStatus::Active, ErrorCode::PositionNotActive);
         let amount = stake_account.amount;
         require!(amount > 0, ErrorCode::NothingToUnstake);
 
-        let mut penalty = 0;
-        let mut final_amount = amount;
+        let (rewards, penalty_share) =
+            settle_position(
+                pool,
+                stake_account,
+                ctx.accounts.reward_vault.amount,
+                clock.unix_timestamp,
+            )?;
+
+        // Out of the pool: no APY, reward-rate weight, penalty share or tier
+        let tier_before = staker_summary.tier.clone();
+        remove_from_position(pool, pool_config, staker_summary, stake_account, amount, clock.unix_timestamp)?;
+        if staker_summary.open_positions == 0 {
+            pool.total_stakers -= 1;
+        }
+        if staker_summary.tier != tier_before {
+            reweight_positions(
+                pool,
+                staker_summary,
+                &stake_account.key(),
+                stake_account.amount > 0,
+                ctx.remaining_accounts,
+                clock.unix_timestamp,
+            )?;
+        }
 
-        // Check if locked
-        if clock.unix_timestamp < stake_account.lock_until {
-            // Early withdrawal penalty: 20%
-            penalty = amount * 20 / 100;
-            final_amount = amount - penalty;
+        stake_account.status = PositionStatus::Cooling;
+        stake_account.cooling_amount = amount;
+        stake_account.cooldown_end = clock.unix_timestamp + pool_config.cooldown_period;
+
+        let pool_key = pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
+        if penalty_share > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, penalty_share)?;
+        }
 
-            msg!(
-                "⚠️ Early withdrawal! Penalty: {} tokens (20%)",
-                penalty
-            );
+        if rewards > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, rewards)?;
         }
 
-        // Transfer tokens back to user
+        emit!(UnstakeRequested {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount,
+            cooldown_end: stake_account.cooldown_end,
+            rewards,
+            penalty_share,
+            unpaid_rewards: stake_account.unpaid_rewards,
+        });
+
+        msg!(
+            "Cooldown started for position #{}: {} tokens withdrawable at {}",
+            stake_account.position_index,
+            amount,
+            stake_account.cooldown_end
+        );
+
+        Ok(())
+    }
+
+    /// Return the tokens of a position whose cooldown is over and close it,
+    /// unless it still holds unpaid rewards
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let clock = Clock::get()?;
+
+        require!(
+            stake_account.status_at(clock.unix_timestamp) == PositionStatus::Withdrawable,
+            ErrorCode::CooldownNotFinished
+        );
+
+        let amount = std::mem::take(&mut stake_account.cooling_amount);
+        let pool_key = ctx.accounts.pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
         let cpi_accounts = Transfer {
             from: ctx.accounts.pool_token_account.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.pool_authority.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, final_amount)?;
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
 
-        // If penalty, keep in pool for other stakers
-        if penalty > 0 {
-            pool.total_penalty_pool += penalty;
+        emit!(Withdrawn {
+            owner: stake_account.owner,
+            position_index: stake_account.position_index,
+            amount,
+        });
+
+        msg!("Withdrew {} tokens from position #{}", amount, stake_account.position_index);
+
+        if ctx.accounts.stake_account.is_closable() {
+            ctx.accounts.stake_account.close(ctx.accounts.owner.to_account_info())?;
         }
 
-        // Update pool
-        pool.total_staked -= amount;
-        pool.total_stakers -= 1;
+        Ok(())
+    }
+
+    /// Early withdrawal penalty for taking `amount` out of a position right
+    /// now. Returned to the caller, meant for UI quotes via simulation.
+    pub fn quote_penalty(ctx: Context<QuotePenalty>, amount: u64) -> Result<u64> {
+        let clock = Clock::get()?;
+        Ok(ctx.accounts.stake_account.early_withdrawal_penalty(amount, clock.unix_timestamp))
+    }
 
-        // Reset stake account
-        stake_account.amount = 0;
-        stake_account.tier = Tier::None;
+    /// Fund the reward vault and emit `amount`, plus whatever is left of the
+    /// current period, evenly over `duration` seconds (reward-rate mode, authority only)
+    pub fn start_reward_period(ctx: Context<StartRewardPeriod>, amount: u64, duration: i64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        require!(pool.reward_mode == RewardMode::RewardRate, ErrorCode::WrongRewardMode);
+        require!(duration > 0, ErrorCode::InvalidRewardPeriod);
 
-        msg!(
-            "Unstaked {} tokens. Received: {} (Penalty: {})",
+        let now = Clock::get()?.unix_timestamp;
+        pool.update_rewards(now)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        pool.total_reward_funded += amount;
+
+        let leftover = if now < pool.reward_period_end {
+            (pool.reward_period_end - now) as u128 * pool.reward_rate as u128
+        } else {
+            0
+        };
+        let reward_rate = ((amount as u128 + leftover) / duration as u128) as u64;
+        require!(reward_rate > 0, ErrorCode::InvalidRewardPeriod);
+
+        // The vault has to hold the whole period's emission up front, on top
+        // of what is already owed to stakers
+        ctx.accounts.reward_vault.reload()?;
+        require!(
+            reward_rate as u128 * duration as u128 <= pool.free_rewards(ctx.accounts.reward_vault.amount) as u128,
+            ErrorCode::InsufficientRewardFunds
+        );
+
+        pool.reward_rate = reward_rate;
+        pool.last_update_time = now;
+        pool.reward_period_end = now + duration;
+
+        emit!(RewardPeriodStarted {
             amount,
-            final_amount,
-            penalty
+            reward_rate,
+            period_end: pool.reward_period_end,
+        });
+
+        msg!(
+            "Reward period started: {} tokens/s until {}",
+            reward_rate,
+            pool.reward_period_end
         );
 
         Ok(())
     }
-}
 
-// ============================================================================
-// HELPER FUNCTIONS
-// ============================================================================
+    /// Top up the reward vault without touching the emission (anyone)
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        require!(amount > 0, ErrorCode::InvalidAmount);
 
-fn calculate_rewards(amount: u64, apy_basis_points: u16, time_staked_seconds: i64) -> u64 {
-    // Formula: (amount * APY * time) / (10000 * 365 * 86400)
-    // time_staked_seconds / (365 * 86400) = fraction of year
-    let annual_reward = (amount as u128) * (apy_basis_points as u128) / 10000;
-    let reward = annual_reward * (time_staked_seconds as u128) / (365 * 86400);
-    reward as u64
-}
+        let now = Clock::get()?.unix_timestamp;
+        pool.update_rewards(now)?;
 
-// ============================================================================
-// CONTEXTS
-// ============================================================================
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        pool.total_reward_funded += amount;
 
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Pool::INIT_SPACE,
-        seeds = [b"pool"],
-        bump
-    )]
-    pub pool: Account<'info, Pool>,
+        ctx.accounts.reward_vault.reload()?;
+        let vault_balance = ctx.accounts.reward_vault.amount;
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        emit!(RewardsFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            vault_balance,
+            reward_liabilities: pool.reward_liabilities,
+            total_reward_funded: pool.total_reward_funded,
+        });
 
-    /// CHECK: Reward vault token account
-    pub reward_vault: AccountInfo<'info>,
+        msg!(
+            "Reward vault funded with {} tokens. Balance: {}, liabilities: {}, runway: {}s",
+            amount,
+            vault_balance,
+            pool.reward_liabilities,
+            pool.reward_runway(vault_balance, now)
+        );
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Stake<'info> {
-    #[account(
-        init_if_needed,
-        payer = owner,
-        space = 8 + StakeAccount::INIT_SPACE,
-        seeds = [b"stake", owner.key().as_ref()],
-        bump
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
+    /// Seconds the reward vault lasts at the current emission once accrued
+    /// liabilities are paid. Returned to the caller, meant for dashboards via
+    /// simulation.
+    pub fn reward_runway(ctx: Context<RewardRunway>) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        // Liabilities as of now, without writing the pool
+        let mut pool = Pool::clone(&ctx.accounts.pool);
+        pool.update_rewards(now)?;
+        Ok(pool.reward_runway(ctx.accounts.reward_vault.amount, now))
+    }
 
-    #[account(
-        mut,
-        seeds = [b"pool"],
-        bump
-    )]
-    pub pool: Account<'info, Pool>,
+    /// Schedule new lock options, tier thresholds (in whole tokens), tier
+    /// multipliers and cooldown, executable once the timelock delay has passed
+    /// (authority only)
+    pub fn queue_config_update(
+        ctx: Context<QueueConfigUpdate>,
+        lock_options: Vec<LockOption>,
+        staker_threshold_tokens: u64,
+        whale_threshold_tokens: u64,
+        tier_multipliers: [u64; 3],
+        cooldown_period: i64,
+    ) -> Result<()> {
+        let decimals = ctx.accounts.pool_config.decimals;
+        let staker_threshold = to_base_units(staker_threshold_tokens, decimals).ok_or(ErrorCode::InvalidPoolConfig)?;
+        let whale_threshold = to_base_units(whale_threshold_tokens, decimals).ok_or(ErrorCode::InvalidPoolConfig)?;
+        require!(
+            validate_config(&lock_options, staker_threshold, whale_threshold, &tier_multipliers, cooldown_period),
+            ErrorCode::InvalidPoolConfig
+        );
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
+        let now = Clock::get()?.unix_timestamp;
+        let eta = now + ctx.accounts.pool.timelock_delay_at(now);
+        let pending_config_update = &mut ctx.accounts.pending_config_update;
+        pending_config_update.lock_options = lock_options;
+        pending_config_update.staker_threshold = staker_threshold;
+        pending_config_update.whale_threshold = whale_threshold;
+        pending_config_update.tier_multipliers = tier_multipliers;
+        pending_config_update.cooldown_period = cooldown_period;
+        pending_config_update.eta = eta;
+        pending_config_update.queued_by = ctx.accounts.authority.key();
+
+        emit!(ConfigUpdateQueued {
+            lock_options: pending_config_update.lock_options.clone(),
+            staker_threshold,
+            whale_threshold,
+            tier_multipliers,
+            cooldown_period,
+            eta,
+        });
 
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+        msg!(
+            "Config update queued - {} lock options, executable at {}",
+            pending_config_update.lock_options.len(),
+            eta
+        );
 
-    #[account(mut)]
-    pub pool_token_account: Account<'info, TokenAccount>,
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// Drop a queued config change before it is executed (authority only)
+    pub fn cancel_config_update(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+        emit!(ConfigUpdateCancelled {
+            eta: ctx.accounts.pending_config_update.eta,
+        });
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(
-        mut,
-        seeds = [b"stake", owner.key().as_ref()],
-        bump,
-        has_one = owner
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
+        msg!("Config update cancelled");
 
-    pub owner: Signer<'info>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    /// Apply a queued config change once its timelock has passed (anyone).
+    /// Open positions keep the lock terms and tier multipliers they were opened with.
+    pub fn execute_config_update(ctx: Context<ExecuteConfigUpdate>) -> Result<()> {
+        let pending_config_update = &ctx.accounts.pending_config_update;
+        require!(
+            Clock::get()?.unix_timestamp >= pending_config_update.eta,
+            ErrorCode::TimelockNotExpired
+        );
 
-    #[account(mut)]
-    pub reward_vault: Account<'info, TokenAccount>,
+        let pool_config = &mut ctx.accounts.pool_config;
+        pool_config.lock_options = pending_config_update.lock_options.clone();
+        pool_config.staker_threshold = pending_config_update.staker_threshold;
+        pool_config.whale_threshold = pending_config_update.whale_threshold;
+        pool_config.tier_multipliers = pending_config_update.tier_multipliers;
+        pool_config.cooldown_period = pending_config_update.cooldown_period;
 
-    /// CHECK: Pool authority PDA
-    pub pool_authority: AccountInfo<'info>,
+        emit!(ConfigUpdated {
+            lock_options: pool_config.lock_options.clone(),
+            staker_threshold: pool_config.staker_threshold,
+            whale_threshold: pool_config.whale_threshold,
+            tier_multipliers: pool_config.tier_multipliers,
+            cooldown_period: pool_config.cooldown_period,
+        });
 
-    pub token_program: Program<'info, Token>,
-}
+        msg!("Config updated - {} lock options", pool_config.lock_options.len());
 
-#[derive(Accounts)]
-pub struct Unstake<'info> {
-    #[account(
-        mut,
-        seeds = [b"stake", owner.key().as_ref()],
-        bump,
-        has_one = owner
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
+        Ok(())
+    }
 
-    #[account(
-        mut,
-        seeds = [b"pool"],
-        bump
-    )]
-    pub pool: Account<'info, Pool>,
+    /// Change the delay applied to newly queued changes (authority only). A
+    /// shorter delay only applies once the current one has passed.
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+        require!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+            ErrorCode::InvalidTimelockDelay
+        );
 
-    pub owner: Signer<'info>,
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        let old_timelock_delay = pool.timelock_delay_at(now);
+        let effective_at = pool.set_timelock_delay(timelock_delay, now);
 
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+        emit!(TimelockDelayUpdated {
+            old_timelock_delay,
+            new_timelock_delay: timelock_delay,
+            effective_at,
+        });
 
-    #[account(mut)]
-    pub pool_token_account: Account<'info, TokenAccount>,
+        msg!("Timelock delay set to {} seconds from {}", timelock_delay, effective_at);
 
-    /// CHECK: Pool authority PDA
-    pub pool_authority: AccountInfo<'info>,
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token>,
+    /// Propose a new authority; it takes over once it calls `accept_authority`.
+    /// Proposing `Pubkey::default()` cancels a pending proposal.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: pool.authority,
+            pending_authority: new_authority,
+        });
+
+        msg!("Authority transfer proposed to: {}", new_authority);
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (proposed authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let old_authority = pool.authority;
+        pool.authority = pool.pending_authority;
+        pool.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: pool.authority,
+        });
+
+        msg!("Authority transferred to: {}", pool.authority);
+
+        Ok(())
+    }
+
+    /// Give up admin control for good (authority only)
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let old_authority = pool.authority;
+        pool.authority = Pubkey::default();
+        pool.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: Pubkey::default(),
+        });
+
+        msg!("Authority renounced");
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Share of the reward-rate emission a position is entitled to
+pub fn stake_weight(amount: u64, lock_weight: u64, tier_multiplier: u64) -> u128 {
+    amount as u128 * lock_weight as u128 * tier_multiplier as u128 / 10000
+}
+
+/// Whether `amount` at `lock_weight` earns any reward-rate weight, even at the
+/// 1x a tier multiplier can drop to
+pub fn has_stake_weight(amount: u64, lock_weight: u64) -> bool {
+    stake_weight(amount, lock_weight, 1) > 0
+}
+
+/// Fixed APY rewards of a position per year, scaled by 10000:
+/// amount x APY (basis points) x tier multiplier
+pub fn apy_rate(amount: u64, apy_basis_points: u16, tier_multiplier: u64) -> u128 {
+    amount as u128 * apy_basis_points as u128 * tier_multiplier as u128
+}
+
+/// Fixed APY rewards earned over `elapsed` seconds at an `apy_rate`, rounded down
+pub fn fixed_apy_emission(rate: u128, elapsed: i64) -> u64 {
+    (rate * elapsed as u128 / (10000 * SECONDS_PER_YEAR)) as u64
+}
+
+/// `tokens` whole tokens in base units of a mint with `decimals`
+pub fn to_base_units(tokens: u64, decimals: u8) -> Option<u64> {
+    10u64.checked_pow(decimals as u32)?.checked_mul(tokens)
+}
+
+/// Checks shared by the defaults and every queued config change
+pub fn validate_config(
+    lock_options: &[LockOption],
+    staker_threshold: u64,
+    whale_threshold: u64,
+    tier_multipliers: &[u64; 3],
+    cooldown_period: i64,
+) -> bool {
+    let lock_options_valid = !lock_options.is_empty()
+        && lock_options.len() <= MAX_LOCK_OPTIONS
+        && lock_options.iter().all(|option| option.duration >= 0 && option.weight > 0)
+        && lock_options
+            .iter()
+            .enumerate()
+            .all(|(i, option)| lock_options[..i].iter().all(|other| other.duration != option.duration));
+
+    lock_options_valid
+        && 0 < staker_threshold
+        && staker_threshold < whale_threshold
+        && 1 <= tier_multipliers[0]
+        && tier_multipliers[0] <= tier_multipliers[1]
+        && tier_multipliers[1] <= tier_multipliers[2]
+        && (0 < cooldown_period && cooldown_period <= MAX_COOLDOWN_PERIOD)
+}
+
+/// Cranker's cut of the rewards it compounds
+pub fn compound_bounty(rewards: u64) -> u64 {
+    (rewards as u128 * COMPOUND_BOUNTY_BP as u128 / 10000) as u64
+}
+
+/// Grow a settled position by `amount` already sitting in the staking vault.
+/// The lock is left as is; tier, weight and debts are brought up to date.
+pub fn add_to_position(
+    pool: &mut Pool,
+    pool_config: &PoolConfig,
+    staker_summary: &mut StakerSummary,
+    stake_account: &mut StakeAccount,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    accrue_position(pool, stake_account, now)?;
+    stake_account.settle_penalty_share(pool.acc_penalty_per_share);
+
+    stake_account.amount += amount;
+    staker_summary.total_amount += amount;
+    staker_summary.tier = pool_config.tier_for_amount(staker_summary.total_amount);
+    pool.total_staked += amount;
+
+    let tier_multiplier = stake_account.tier_multiplier(&staker_summary.tier);
+    refresh_rates(pool, stake_account, tier_multiplier);
+    stake_account.reset_penalty_debt(pool.acc_penalty_per_share);
+    Ok(())
+}
+
+/// Shrink a settled position by `amount`. The position stops counting
+/// towards the owner's open positions once it is empty.
+pub fn remove_from_position(
+    pool: &mut Pool,
+    pool_config: &PoolConfig,
+    staker_summary: &mut StakerSummary,
+    stake_account: &mut StakeAccount,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    accrue_position(pool, stake_account, now)?;
+    stake_account.settle_penalty_share(pool.acc_penalty_per_share);
+
+    stake_account.amount -= amount;
+    if stake_account.amount == 0 {
+        staker_summary.remove_position(amount, pool_config);
+    } else {
+        staker_summary.total_amount -= amount;
+        staker_summary.tier = pool_config.tier_for_amount(staker_summary.total_amount);
+    }
+    pool.total_staked -= amount;
+
+    let tier_multiplier = stake_account.tier_multiplier(&staker_summary.tier);
+    refresh_rates(pool, stake_account, tier_multiplier);
+    stake_account.reset_penalty_debt(pool.acc_penalty_per_share);
+    Ok(())
+}
+
+/// Recompute a position's weight and APY rate for `tier_multiplier`. What it
+/// earned at the old ones has to be accrued first.
+fn refresh_rates(pool: &mut Pool, stake_account: &mut StakeAccount, tier_multiplier: u64) {
+    pool.total_weight -= stake_account.weight;
+    stake_account.weight = stake_weight(stake_account.amount, stake_account.lock_weight, tier_multiplier);
+    pool.total_weight += stake_account.weight;
+
+    pool.total_apy_rate -= stake_account.apy_rate;
+    stake_account.apy_rate = apy_rate(stake_account.amount, stake_account.apy_basis_points, tier_multiplier);
+    pool.total_apy_rate += stake_account.apy_rate;
+
+    stake_account.reset_reward_debt(pool.acc_reward_per_weight);
+}
+
+/// Accrue and re-weight the owner's other open positions after their tier
+/// changed, so none of them keeps earning at the old multiplier.
+/// `positions` has to be every open position of the owner but `touched`,
+/// the one the instruction itself changed.
+pub fn reweight_positions(
+    pool: &mut Pool,
+    staker_summary: &StakerSummary,
+    touched: &Pubkey,
+    touched_open: bool,
+    positions: &[AccountInfo],
+    now: i64,
+) -> Result<()> {
+    // Distinct open positions of the owner are all of them once the count matches
+    require!(
+        positions.len() as u32 + touched_open as u32 == staker_summary.open_positions,
+        ErrorCode::MissingPositions
+    );
+
+    let mut seen: Vec<&Pubkey> = Vec::with_capacity(positions.len());
+
+    for info in positions {
+        require!(
+            info.owner == &crate::ID && info.is_writable && info.key != touched && !seen.contains(&info.key),
+            ErrorCode::MissingPositions
+        );
+        seen.push(info.key);
+
+        let mut position = StakeAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            position.owner == staker_summary.owner && position.amount > 0,
+            ErrorCode::MissingPositions
+        );
+
+        accrue_position(pool, &mut position, now)?;
+        let tier_multiplier = position.tier_multiplier(&staker_summary.tier);
+        refresh_rates(pool, &mut position, tier_multiplier);
+        position.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Bring the pool up to `now` and move what a position earned at its current
+/// APY rate or weight into `pending_rewards`; call before either changes
+pub fn accrue_position(pool: &mut Pool, stake_account: &mut StakeAccount, now: i64) -> Result<()> {
+    pool.update_rewards(now)?;
+    match pool.reward_mode {
+        RewardMode::FixedApy => stake_account.accrue_fixed_apy(now),
+        RewardMode::RewardRate => stake_account.settle_rewards(pool.acc_reward_per_weight)?,
+    }
+    Ok(())
+}
+
+/// Settle everything a position has earned up to `now` and take it out for
+/// payout, as (rewards, penalty share). Rewards are capped at what the reward
+/// vault holds; the rest is kept as an IOU on the position for a later payout.
+fn settle_position(
+    pool: &mut Pool,
+    stake_account: &mut StakeAccount,
+    reward_vault_balance: u64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    // Tier multipliers are already part of the APY rate and stake weight
+    accrue_position(pool, stake_account, now)?;
+    let earned = std::mem::take(&mut stake_account.pending_rewards);
+
+    let owed = earned + stake_account.unpaid_rewards;
+    let rewards = owed.min(reward_vault_balance);
+    stake_account.unpaid_rewards = owed - rewards;
+    // Positions round their accruals down and the pool rounds up, so what a
+    // position is paid is always part of the liabilities
+    pool.reward_liabilities = pool
+        .reward_liabilities
+        .checked_sub(rewards)
+        .ok_or(ErrorCode::LiabilityUnderflow)?;
+
+    // Share of early withdrawal penalties, held in the pool vault
+    stake_account.settle_penalty_share(pool.acc_penalty_per_share);
+    let penalty_share = std::mem::take(&mut stake_account.pending_penalty);
+    pool.total_penalty_pool -= penalty_share;
+
+    stake_account.last_claim = now;
+    stake_account.total_claimed += rewards + penalty_share;
+
+    Ok((rewards, penalty_share))
+}
+
+/// Penalty on withdrawing `amount` at `now`: `max_penalty_bp` at lock start,
+/// falling linearly to 0 at `start_time + lock_duration`
+pub fn early_withdrawal_penalty(amount: u64, start_time: i64, lock_duration: i64, now: i64, max_penalty_bp: u64) -> u64 {
+    if lock_duration <= 0 {
+        return 0;
+    }
+
+    let elapsed = (now - start_time).clamp(0, lock_duration);
+    let remaining = (lock_duration - elapsed) as u128;
+    (amount as u128 * max_penalty_bp as u128 * remaining / (10000 * lock_duration as u128)) as u64
+}
+
+/// Shared by `close_position` and `unstake_partial`
+fn unstake_from_position(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let staker_summary = &mut ctx.accounts.staker_summary;
+    let pool = &mut ctx.accounts.pool;
+    let pool_config = &ctx.accounts.pool_config;
+    let clock = Clock::get()?;
+
+    require!(stake_account.amount > 0, ErrorCode::NothingToUnstake);
+    require!(amount > 0 && amount <= stake_account.amount, ErrorCode::InvalidAmount);
+    require!(!stake_account.is_flexible(), ErrorCode::CooldownRequired);
+    let remaining = stake_account.amount - amount;
+    require!(
+        remaining == 0 || has_stake_weight(remaining, stake_account.lock_weight),
+        ErrorCode::PositionTooSmall
+    );
+
+    // Early withdrawal penalty, shrinking as the lock runs out
+    let penalty = stake_account.early_withdrawal_penalty(amount, clock.unix_timestamp);
+    let final_amount = amount - penalty;
+
+    if penalty > 0 {
+        msg!(
+            "⚠️ Early withdrawal! Penalty: {} tokens",
+            penalty
+        );
+    }
+
+    // Pay out everything earned on the current amount before it shrinks
+    let (rewards, penalty_share) =
+        settle_position(
+            pool,
+            stake_account,
+            ctx.accounts.reward_vault.amount,
+            clock.unix_timestamp,
+        )?;
+
+    let tier_before = staker_summary.tier.clone();
+    remove_from_position(pool, pool_config, staker_summary, stake_account, amount, clock.unix_timestamp)?;
+    if staker_summary.open_positions == 0 {
+        pool.total_stakers -= 1;
+    }
+    if staker_summary.tier != tier_before {
+        reweight_positions(
+            pool,
+            staker_summary,
+            &stake_account.key(),
+            stake_account.amount > 0,
+            ctx.remaining_accounts,
+            clock.unix_timestamp,
+        )?;
+    }
+
+    // If penalty, keep in pool for the remaining stakers
+    if penalty > 0 {
+        pool.distribute_penalty(penalty);
+    }
+
+    // Transfer principal and penalty share back to user
+    let pool_key = pool.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"pool_authority", pool_key.as_ref(), &[ctx.bumps.pool_authority]]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.pool_token_account.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.pool_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, final_amount + penalty_share)?;
+
+    if rewards > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, rewards)?;
+    }
+
+    emit!(Unstaked {
+        owner: stake_account.owner,
+        position_index: stake_account.position_index,
+        amount,
+        received: final_amount,
+        penalty,
+        rewards,
+        penalty_share,
+        remaining: stake_account.amount,
+        pool_total_staked: pool.total_staked,
+        total_penalty_pool: pool.total_penalty_pool,
+        unpaid_rewards: stake_account.unpaid_rewards,
+    });
+
+    msg!(
+        "Unstaked {} tokens from position #{}. Received: {} (Penalty: {}) + {} rewards + {} penalty share. Remaining: {}",
+        amount,
+        stake_account.position_index,
+        final_amount,
+        penalty,
+        rewards,
+        penalty_share,
+        stake_account.amount
+    );
+
+    // An empty position can go once no IOU is left on it
+    if ctx.accounts.stake_account.is_closable() {
+        ctx.accounts.stake_account.close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// CONTEXTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: PDA that owns both vaults, signs every outbound transfer
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PoolConfig::INIT_SPACE,
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// Holds all staked tokens
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_vault", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// Holds the tokens paid out as rewards
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakerSummary::INIT_SPACE,
+        seeds = [b"staker", owner.key().as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", owner.key().as_ref(), &staker_summary.next_position_index.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Tier reported with the position's multiplier
+    #[account(
+        seeds = [b"staker", owner.key().as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// Receives the rent of a drained, unstaked position
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Pays out the penalty share
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of both vaults
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", owner.key().as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of both vaults
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankCompound<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", stake_account.owner.as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", stake_account.owner.as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// Anyone; receives the bounty
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = pool.mint
+    )]
+    pub cranker_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of both vaults
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", owner.key().as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of both vaults
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePenalty<'info> {
+    pub stake_account: Account<'info, StakeAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", owner.key().as_ref()],
+        bump
+    )]
+    pub staker_summary: Account<'info, StakerSummary>,
+
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of both vaults
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref(), &stake_account.position_index.to_le_bytes()],
+        bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.pool_token_account @ ErrorCode::InvalidVault
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA, owner of the pool vault
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StartRewardPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub funder: Signer<'info>,
+
+    #[account(mut)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = pool.reward_vault @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RewardRunway<'info> {
+    #[account(
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pool.reward_vault @ ErrorCode::InvalidVault)]
+    pub reward_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct QueueConfigUpdate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingConfigUpdate::INIT_SPACE,
+        seeds = [b"pending_config_update", pool.key().as_ref()],
+        bump
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_config_update", pool.key().as_ref()],
+        bump
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigUpdate<'info> {
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_config_update", pool.key().as_ref()],
+        bump,
+        has_one = queued_by
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_config", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: Gets the pending update's rent back
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump,
+        constraint = pool.pending_authority == new_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool"],
+        bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
 }
 
 // ============================================================================
@@ -364,12 +1893,262 @@ pub struct Pool {
     pub total_staked: u64,
     pub total_stakers: u64,
     pub total_penalty_pool: u64,
+    pub pending_authority: Pubkey, // Default pubkey when no transfer is pending
+    pub mint: Pubkey,
+    pub pool_token_account: Pubkey,
+    pub acc_penalty_per_share: u128, // Penalty per staked token, scaled by PENALTY_PRECISION
+    pub undistributed_penalty: u64,  // Penalties collected while nobody was staked
+    pub reward_mode: RewardMode,
+    pub reward_rate: u64,             // Tokens emitted per second (reward-rate mode)
+    pub reward_period_end: i64,
+    pub last_update_time: i64,
+    pub acc_reward_per_weight: u128,  // Reward per unit of weight, scaled by REWARD_PRECISION
+    pub total_weight: u128,
+    pub timelock_delay: i64,          // Seconds between queueing and executing a config change
+    pub total_apy_rate: u128,         // Sum of the positions' `apy_rate` (fixed APY mode)
+    pub reward_liabilities: u64,      // Rewards accrued to stakers but not paid out yet
+    pub total_reward_funded: u64,     // Recorded deposits into the reward vault
+    pub pending_timelock_delay: i64,  // Shorter delay waiting out the current one; 0 when none
+    pub timelock_delay_eta: i64,      // When `pending_timelock_delay` takes over
+}
+
+impl Pool {
+    /// Delay for changes queued at `now`: a pending shorter delay takes over
+    /// once the delay it replaces has passed
+    pub fn timelock_delay_at(&self, now: i64) -> i64 {
+        if self.pending_timelock_delay > 0 && now >= self.timelock_delay_eta {
+            self.pending_timelock_delay
+        } else {
+            self.timelock_delay
+        }
+    }
+
+    /// Change the delay for changes queued from `now` on and return when it
+    /// applies. A longer delay applies right away; a shorter one only once the
+    /// current delay has passed, so stakers always get the notice they were
+    /// promised.
+    pub fn set_timelock_delay(&mut self, timelock_delay: i64, now: i64) -> i64 {
+        let current = self.timelock_delay_at(now);
+        self.timelock_delay = current.max(timelock_delay);
+        if timelock_delay >= current {
+            self.pending_timelock_delay = 0;
+            self.timelock_delay_eta = now;
+        } else {
+            self.pending_timelock_delay = timelock_delay;
+            self.timelock_delay_eta = now + current;
+        }
+        self.timelock_delay_eta
+    }
+
+    /// Credit a penalty pro rata to everyone still staked. With nobody left it
+    /// is carried over to the next distribution. Rounding dust stays in the
+    /// pool, counted in `total_penalty_pool` but never credited.
+    pub fn distribute_penalty(&mut self, penalty: u64) {
+        self.total_penalty_pool += penalty;
+        self.undistributed_penalty += penalty;
+
+        if self.total_staked == 0 {
+            return;
+        }
+
+        self.acc_penalty_per_share +=
+            self.undistributed_penalty as u128 * PENALTY_PRECISION / self.total_staked as u128;
+        self.undistributed_penalty = 0;
+    }
+
+    /// Accrue the emission up to `now` into `reward_liabilities`, and in
+    /// reward-rate mode into the reward-per-weight accumulator. Time with
+    /// nobody staked is not credited to anyone, and below
+    /// `REWARD_WEIGHT_FLOOR` only the staked weight's share of the floor is.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        if self.reward_mode == RewardMode::FixedApy {
+            if now > self.last_update_time {
+                // Rounded up, covering the positions' rounded-down accruals
+                let emitted = self.total_apy_rate * (now - self.last_update_time) as u128;
+                self.reward_liabilities += emitted.div_ceil(10000 * SECONDS_PER_YEAR) as u64;
+                self.last_update_time = now;
+            }
+            return Ok(());
+        }
+
+        let applicable = now.min(self.reward_period_end);
+        if applicable <= self.last_update_time {
+            return Ok(());
+        }
+
+        let elapsed = applicable - self.last_update_time;
+        self.last_update_time = applicable;
+
+        if self.total_weight == 0 {
+            return Ok(());
+        }
+
+        let emitted = elapsed as u128 * self.reward_rate as u128;
+        let split_weight = self.total_weight.max(REWARD_WEIGHT_FLOOR);
+        self.acc_reward_per_weight = emitted
+            .checked_mul(REWARD_PRECISION)
+            .and_then(|scaled| self.acc_reward_per_weight.checked_add(scaled / split_weight))
+            .ok_or(ErrorCode::RewardOverflow)?;
+        // At most the period's emission, which start_reward_period checked
+        // against the vault. Under the floor the credited share is rounded up,
+        // covering the positions' rounded-down accruals.
+        let credited = if self.total_weight < REWARD_WEIGHT_FLOOR {
+            (emitted * self.total_weight).div_ceil(REWARD_WEIGHT_FLOOR)
+        } else {
+            emitted
+        };
+        self.reward_liabilities += credited as u64;
+        Ok(())
+    }
+
+    /// Part of the reward vault not owed to stakers yet
+    pub fn free_rewards(&self, reward_vault_balance: u64) -> u64 {
+        reward_vault_balance.saturating_sub(self.reward_liabilities)
+    }
+
+    /// Seconds until the current emission outgrows what the reward vault holds
+    /// beyond `reward_liabilities`; `u64::MAX` while nothing is being emitted.
+    /// Call after `update_rewards(now)`.
+    pub fn reward_runway(&self, reward_vault_balance: u64, now: i64) -> u64 {
+        let free = self.free_rewards(reward_vault_balance) as u128;
+
+        // Emission per second as rate / scale
+        let (rate, scale) = match self.reward_mode {
+            RewardMode::FixedApy => (self.total_apy_rate, 10000 * SECONDS_PER_YEAR),
+            RewardMode::RewardRate if now < self.reward_period_end && self.total_weight > 0 => {
+                (self.reward_rate as u128, 1)
+            }
+            RewardMode::RewardRate => (0, 1),
+        };
+        if rate == 0 {
+            return u64::MAX;
+        }
+
+        (free * scale / rate).min(u64::MAX as u128) as u64
+    }
+}
+
+/// Staking terms, changed only through the timelock. Updates apply to new
+/// positions; open positions keep their lock duration, APY, lock weight and
+/// tier multipliers.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolConfig {
+    pub pool: Pubkey,
+    pub decimals: u8,               // Mint decimals, scale of the thresholds
+    #[max_len(MAX_LOCK_OPTIONS)]
+    pub lock_options: Vec<LockOption>,
+    pub staker_threshold: u64,      // In base units
+    pub whale_threshold: u64,
+    pub tier_multipliers: [u64; 3], // None / Staker / Whale
+    pub cooldown_period: i64,       // Seconds, flexible positions only
+}
+
+impl PoolConfig {
+    /// Default lock options and tiers for a pool whose mint has `decimals`
+    pub fn with_defaults(pool: Pubkey, decimals: u8) -> Option<Self> {
+        let lock_options = vec![
+            LockOption { duration: LOCK_FLEXIBLE, apy_basis_points: APY_FLEXIBLE, weight: WEIGHT_FLEXIBLE },
+            LockOption { duration: LOCK_30_DAYS, apy_basis_points: APY_30_DAYS, weight: WEIGHT_30_DAYS },
+            LockOption { duration: LOCK_90_DAYS, apy_basis_points: APY_90_DAYS, weight: WEIGHT_90_DAYS },
+            LockOption { duration: LOCK_180_DAYS, apy_basis_points: APY_180_DAYS, weight: WEIGHT_180_DAYS },
+            LockOption { duration: LOCK_365_DAYS, apy_basis_points: APY_365_DAYS, weight: WEIGHT_365_DAYS },
+        ];
+
+        Some(Self {
+            pool,
+            decimals,
+            lock_options,
+            staker_threshold: to_base_units(STAKER_THRESHOLD_TOKENS, decimals)?,
+            whale_threshold: to_base_units(WHALE_THRESHOLD_TOKENS, decimals)?,
+            tier_multipliers: TIER_MULTIPLIERS,
+            cooldown_period: DEFAULT_COOLDOWN_PERIOD,
+        })
+    }
+
+    /// Terms offered for a lock duration, if any
+    pub fn lock_option(&self, duration: i64) -> Option<&LockOption> {
+        self.lock_options.iter().find(|option| option.duration == duration)
+    }
+
+    /// Tier reached by the sum of a wallet's open positions
+    pub fn tier_for_amount(&self, total_amount: u64) -> Tier {
+        if total_amount >= self.whale_threshold {
+            Tier::Whale
+        } else if total_amount >= self.staker_threshold {
+            Tier::Staker
+        } else {
+            Tier::None
+        }
+    }
+
+    /// Reward multiplier of a tier
+    pub fn tier_multiplier(&self, tier: &Tier) -> u64 {
+        match tier {
+            Tier::None => self.tier_multipliers[0],
+            Tier::Staker => self.tier_multipliers[1],
+            Tier::Whale => self.tier_multipliers[2],
+        }
+    }
+}
+
+/// Config change waiting for its timelock
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigUpdate {
+    #[max_len(MAX_LOCK_OPTIONS)]
+    pub lock_options: Vec<LockOption>,
+    pub staker_threshold: u64,      // In base units
+    pub whale_threshold: u64,
+    pub tier_multipliers: [u64; 3],
+    pub cooldown_period: i64,
+    pub eta: i64,                   // Earliest execution time
+    pub queued_by: Pubkey,          // Refunded the rent on execution
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct LockOption {
+    pub duration: i64,         // Seconds, 0 = flexible
+    pub apy_basis_points: u16, // Fixed APY mode
+    pub weight: u64,           // Reward-rate mode, basis points (10000 = 1x)
+}
+
+/// Per-wallet totals across all open positions, seeded by owner
+#[account]
+#[derive(InitSpace)]
+pub struct StakerSummary {
+    pub owner: Pubkey,
+    pub total_amount: u64,        // Sum of all open positions
+    pub open_positions: u32,
+    pub next_position_index: u64, // Indexes are never reused
+    pub tier: Tier,               // From total_amount
+}
+
+impl StakerSummary {
+    /// Count a newly opened position and return its index
+    pub fn add_position(&mut self, amount: u64, pool_config: &PoolConfig) -> u64 {
+        let position_index = self.next_position_index;
+        self.next_position_index += 1;
+        self.open_positions += 1;
+        self.total_amount += amount;
+        self.tier = pool_config.tier_for_amount(self.total_amount);
+        position_index
+    }
+
+    /// Drop a closed position from the totals
+    pub fn remove_position(&mut self, amount: u64, pool_config: &PoolConfig) {
+        self.open_positions -= 1;
+        self.total_amount -= amount;
+        self.tier = pool_config.tier_for_amount(self.total_amount);
+    }
 }
 
+/// A single stake position, seeded by owner and position index
 #[account]
 #[derive(InitSpace)]
 pub struct StakeAccount {
     pub owner: Pubkey,
+    pub position_index: u64,
     pub amount: u64,
     pub start_time: i64,
     pub lock_until: i64,
@@ -377,7 +2156,121 @@ pub struct StakeAccount {
     pub last_claim: i64,
     pub total_claimed: u64,
     pub apy_basis_points: u16,
-    pub tier: Tier,
+    pub lock_weight: u64,     // Reward-rate lock weight (basis points), fixed when opened
+    pub penalty_debt: u128,   // amount * acc_penalty_per_share at the last settlement
+    pub pending_penalty: u64, // Settled penalty share not yet claimed
+    pub weight: u128,         // amount x lock weight x owner tier multiplier at the last update
+    pub reward_per_weight_paid: u128, // acc_reward_per_weight at the last settlement
+    pub pending_rewards: u64, // Settled rewards not yet claimed
+    pub auto_compound: bool,  // Crankable by anyone for a bounty
+    pub status: PositionStatus,
+    pub cooling_amount: u64,  // Waiting out the cooldown, no longer staked
+    pub cooldown_end: i64,
+    pub apy_rate: u128,       // amount x APY x owner tier multiplier at the last update
+    pub unpaid_rewards: u64,  // IOU: rewards the reward vault could not cover yet
+    pub last_accrual: i64,    // Fixed APY accrued into pending_rewards up to here
+    pub tier_multipliers: [u64; 3], // Pool config's None / Staker / Whale multipliers when opened
+}
+
+impl StakeAccount {
+    /// No lock: leaves through `request_unstake` and `withdraw`
+    pub fn is_flexible(&self) -> bool {
+        self.lock_duration == 0
+    }
+
+    /// Nothing staked, cooling down or owed to the owner any more
+    pub fn is_closable(&self) -> bool {
+        self.amount == 0 && self.cooling_amount == 0 && self.unpaid_rewards == 0
+    }
+
+    /// Stored status, with a finished cooldown reported as `Withdrawable`
+    pub fn status_at(&self, now: i64) -> PositionStatus {
+        match self.status {
+            PositionStatus::Cooling if now >= self.cooldown_end => PositionStatus::Withdrawable,
+            status => status,
+        }
+    }
+
+    /// Multiplier of `tier` on the terms this position was opened with
+    pub fn tier_multiplier(&self, tier: &Tier) -> u64 {
+        match tier {
+            Tier::None => self.tier_multipliers[0],
+            Tier::Staker => self.tier_multipliers[1],
+            Tier::Whale => self.tier_multipliers[2],
+        }
+    }
+
+    /// Early withdrawal penalty for taking `amount` out of this position at `now`
+    pub fn early_withdrawal_penalty(&self, amount: u64, now: i64) -> u64 {
+        early_withdrawal_penalty(amount, self.start_time, self.lock_duration, now, MAX_EARLY_PENALTY_BP)
+    }
+
+    /// Penalty share earned since the last settlement
+    pub fn accrued_penalty_share(&self, acc_penalty_per_share: u128) -> u64 {
+        (self.amount as u128 * acc_penalty_per_share / PENALTY_PRECISION - self.penalty_debt) as u64
+    }
+
+    /// Move the earned penalty share into `pending_penalty`; call before `amount` changes
+    pub fn settle_penalty_share(&mut self, acc_penalty_per_share: u128) {
+        self.pending_penalty += self.accrued_penalty_share(acc_penalty_per_share);
+        self.reset_penalty_debt(acc_penalty_per_share);
+    }
+
+    /// Start accruing from the current accumulator; call after `amount` changes
+    pub fn reset_penalty_debt(&mut self, acc_penalty_per_share: u128) {
+        self.penalty_debt = self.amount as u128 * acc_penalty_per_share / PENALTY_PRECISION;
+    }
+
+    /// Reward-rate rewards earned since the last settlement. Only the growth of
+    /// the accumulator since then is multiplied by the weight, which the
+    /// position's share of the emission keeps in range.
+    pub fn accrued_rewards(&self, acc_reward_per_weight: u128) -> Result<u64> {
+        self.weight
+            .checked_mul(acc_reward_per_weight - self.reward_per_weight_paid)
+            .and_then(|scaled| u64::try_from(scaled / REWARD_PRECISION).ok())
+            .ok_or_else(|| error!(ErrorCode::RewardOverflow))
+    }
+
+    /// Move the earned rewards into `pending_rewards`; call before `weight` changes
+    pub fn settle_rewards(&mut self, acc_reward_per_weight: u128) -> Result<()> {
+        let earned = self.accrued_rewards(acc_reward_per_weight)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(earned)
+            .ok_or(ErrorCode::RewardOverflow)?;
+        self.reset_reward_debt(acc_reward_per_weight);
+        Ok(())
+    }
+
+    /// Start accruing from the current accumulator; call after `weight` changes
+    pub fn reset_reward_debt(&mut self, acc_reward_per_weight: u128) {
+        self.reward_per_weight_paid = acc_reward_per_weight;
+    }
+
+    /// Fixed APY rewards earned at `apy_rate` since the last accrual
+    pub fn accrued_fixed_apy(&self, now: i64) -> u64 {
+        fixed_apy_emission(self.apy_rate, now - self.last_accrual)
+    }
+
+    /// Move the earned fixed APY rewards into `pending_rewards`; call before `apy_rate` changes
+    pub fn accrue_fixed_apy(&mut self, now: i64) {
+        self.pending_rewards += self.accrued_fixed_apy(now);
+        self.last_accrual = now;
+    }
+}
+
+/// Active -> Cooling (request_unstake) -> Withdrawable (cooldown over) -> closed (withdraw)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PositionStatus {
+    Active,
+    Cooling,
+    Withdrawable, // Never stored, see `StakeAccount::status_at`
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RewardMode {
+    FixedApy,   // APY x tier multiplier, paid from the vault as it accrues
+    RewardRate, // Funded emission per second, split by stake weight
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
@@ -387,6 +2280,154 @@ pub enum Tier {
     Whale,   // 100k+ tokens = 5x rewards
 }
 
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[event]
+pub struct PoolInitialized {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub pool_token_account: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reward_mode: RewardMode,
+}
+
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub total_amount: u64, // Across all of the owner's positions
+    pub lock_duration: i64,
+    pub lock_until: i64,
+    pub apy_basis_points: u16,
+    pub tier: Tier,
+    pub pool_total_staked: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub multiplier: u64,
+    pub time_staked: i64,
+    pub total_claimed: u64,
+    pub penalty_share: u64,
+    pub unpaid_rewards: u64, // Left as an IOU when the reward vault is short
+}
+
+#[event]
+pub struct Compounded {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,          // Added to the position
+    pub bounty: u64,          // Paid to the cranker, 0 when the owner compounds
+    pub cranker: Pubkey,
+    pub position_amount: u64,
+    pub tier: Tier,
+}
+
+#[event]
+pub struct AutoCompoundSet {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub received: u64,
+    pub penalty: u64,
+    pub rewards: u64,
+    pub penalty_share: u64,
+    pub remaining: u64,       // Left in the position, closed at 0
+    pub pool_total_staked: u64,
+    pub total_penalty_pool: u64,
+    pub unpaid_rewards: u64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+    pub cooldown_end: i64,
+    pub rewards: u64,       // Paid out when the cooldown starts
+    pub penalty_share: u64,
+    pub unpaid_rewards: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub owner: Pubkey,
+    pub position_index: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardPeriodStarted {
+    pub amount: u64,
+    pub reward_rate: u64,
+    pub period_end: i64,
+}
+
+#[event]
+pub struct RewardsFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub reward_liabilities: u64,
+    pub total_reward_funded: u64,
+}
+
+#[event]
+pub struct ConfigUpdateQueued {
+    pub lock_options: Vec<LockOption>,
+    pub staker_threshold: u64,
+    pub whale_threshold: u64,
+    pub tier_multipliers: [u64; 3],
+    pub cooldown_period: i64,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigUpdateCancelled {
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub lock_options: Vec<LockOption>,
+    pub staker_threshold: u64,
+    pub whale_threshold: u64,
+    pub tier_multipliers: [u64; 3],
+    pub cooldown_period: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_timelock_delay: i64,
+    pub new_timelock_delay: i64,
+    pub effective_at: i64, // Later than now when the delay is shortened
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 // ============================================================================
 // ERRORS
 // ============================================================================
@@ -396,7 +2437,7 @@ pub enum ErrorCode {
     #[msg("Invalid stake amount")]
     InvalidAmount,
 
-    #[msg("Invalid lock duration (must be 30, 90, 180, or 365 days)")]
+    #[msg("Lock duration is not one of the configured lock options")]
     InvalidLockDuration,
 
     #[msg("No rewards to claim")]
@@ -404,4 +2445,61 @@ pub enum ErrorCode {
 
     #[msg("Nothing to unstake")]
     NothingToUnstake,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Vault does not belong to this pool")]
+    InvalidVault,
+
+    #[msg("Instruction not available in this pool's reward mode")]
+    WrongRewardMode,
+
+    #[msg("Reward period must be positive and emit at least one token per second")]
+    InvalidRewardPeriod,
+
+    #[msg("Reward vault cannot cover the reward period")]
+    InsufficientRewardFunds,
+
+    #[msg("Position has not enabled auto-compounding")]
+    AutoCompoundDisabled,
+
+    #[msg("Position was compounded or claimed too recently")]
+    CompoundTooSoon,
+
+    #[msg("Invalid pool config (lock options, tier thresholds or multipliers)")]
+    InvalidPoolConfig,
+
+    #[msg("Timelock has not expired yet")]
+    TimelockNotExpired,
+
+    #[msg("Timelock delay must be between 1 and 30 days")]
+    InvalidTimelockDelay,
+
+    #[msg("Only flexible positions have a cooldown")]
+    NotFlexiblePosition,
+
+    #[msg("Flexible positions leave through request_unstake and withdraw")]
+    CooldownRequired,
+
+    #[msg("Position is cooling down")]
+    PositionNotActive,
+
+    #[msg("Position is not withdrawable yet")]
+    CooldownNotFinished,
+
+    #[msg("Wallet already has the maximum number of open positions")]
+    TooManyPositions,
+
+    #[msg("A tier change needs all of the owner's other open positions as remaining accounts")]
+    MissingPositions,
+
+    #[msg("Reward payout exceeds the pool's accrued liabilities")]
+    LiabilityUnderflow,
+
+    #[msg("Position is too small to earn any stake weight")]
+    PositionTooSmall,
+
+    #[msg("Reward accounting overflowed")]
+    RewardOverflow,
 }
@@ -0,0 +1,337 @@
+// Staking Event Tests
+// Emits events through the shared log-capturing syscall stub and decodes them
+// back from the "Program data:" lines, the same way the indexer does.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Event};
+use event_testing::{capture_logs, decode_event, event_discriminator};
+use staking::{
+    AuthorityProposed, AuthorityTransferred, AutoCompoundSet, Compounded, ConfigUpdateCancelled, ConfigUpdateQueued,
+    ConfigUpdated, LockOption, PoolInitialized, RewardMode, RewardPeriodStarted, RewardsClaimed, Staked, Tier,
+    RewardsFunded, TimelockDelayUpdated, UnstakeRequested, Unstaked, Withdrawn,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_discriminators() {
+        assert_eq!(AuthorityProposed::DISCRIMINATOR, event_discriminator("AuthorityProposed"));
+        assert_eq!(AuthorityTransferred::DISCRIMINATOR, event_discriminator("AuthorityTransferred"));
+        assert_eq!(PoolInitialized::DISCRIMINATOR, event_discriminator("PoolInitialized"));
+        assert_eq!(RewardPeriodStarted::DISCRIMINATOR, event_discriminator("RewardPeriodStarted"));
+        assert_eq!(Staked::DISCRIMINATOR, event_discriminator("Staked"));
+        assert_eq!(RewardsClaimed::DISCRIMINATOR, event_discriminator("RewardsClaimed"));
+        assert_eq!(Unstaked::DISCRIMINATOR, event_discriminator("Unstaked"));
+        assert_eq!(Compounded::DISCRIMINATOR, event_discriminator("Compounded"));
+        assert_eq!(AutoCompoundSet::DISCRIMINATOR, event_discriminator("AutoCompoundSet"));
+        assert_eq!(ConfigUpdateQueued::DISCRIMINATOR, event_discriminator("ConfigUpdateQueued"));
+        assert_eq!(ConfigUpdateCancelled::DISCRIMINATOR, event_discriminator("ConfigUpdateCancelled"));
+        assert_eq!(ConfigUpdated::DISCRIMINATOR, event_discriminator("ConfigUpdated"));
+        assert_eq!(TimelockDelayUpdated::DISCRIMINATOR, event_discriminator("TimelockDelayUpdated"));
+        assert_eq!(UnstakeRequested::DISCRIMINATOR, event_discriminator("UnstakeRequested"));
+        assert_eq!(Withdrawn::DISCRIMINATOR, event_discriminator("Withdrawn"));
+        assert_eq!(RewardsFunded::DISCRIMINATOR, event_discriminator("RewardsFunded"));
+    }
+
+    #[test]
+    fn test_staked_event() {
+        let owner = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(Staked {
+                owner,
+                position_index: 2,
+                amount: 10_000,
+                total_amount: 25_000,
+                lock_duration: 90 * 86400,
+                lock_until: 1_707_776_000,
+                apy_basis_points: 4000,
+                tier: Tier::Staker,
+                pool_total_staked: 1_000_000,
+            });
+        });
+
+        let event: Staked = decode_event(&logs).expect("Staked not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.position_index, 2);
+        assert_eq!(event.amount, 10_000);
+        assert_eq!(event.total_amount, 25_000);
+        assert_eq!(event.lock_duration, 90 * 86400);
+        assert_eq!(event.lock_until, 1_707_776_000);
+        assert_eq!(event.apy_basis_points, 4000);
+        assert_eq!(event.tier, Tier::Staker);
+        assert_eq!(event.pool_total_staked, 1_000_000);
+    }
+
+    #[test]
+    fn test_rewards_claimed_event() {
+        let logs = capture_logs(|| {
+            emit!(RewardsClaimed {
+                owner: Pubkey::new_unique(),
+                position_index: 0,
+                amount: 5_000,
+                multiplier: 5,
+                time_staked: 86400,
+                total_claimed: 12_000,
+                penalty_share: 300,
+                unpaid_rewards: 0,
+            });
+        });
+
+        let event: RewardsClaimed = decode_event(&logs).expect("RewardsClaimed not found in logs");
+        assert_eq!(event.amount, 5_000);
+        assert_eq!(event.multiplier, 5);
+        assert_eq!(event.time_staked, 86400);
+        assert_eq!(event.total_claimed, 12_000);
+        assert_eq!(event.penalty_share, 300);
+    }
+
+    #[test]
+    fn test_unstaked_event() {
+        let logs = capture_logs(|| {
+            emit!(Unstaked {
+                owner: Pubkey::new_unique(),
+                position_index: 1,
+                amount: 1_000,
+                received: 800,
+                penalty: 200,
+                rewards: 50,
+                penalty_share: 10,
+                remaining: 0,
+                pool_total_staked: 9_000,
+                total_penalty_pool: 200,
+                unpaid_rewards: 25,
+            });
+        });
+
+        let event: Unstaked = decode_event(&logs).expect("Unstaked not found in logs");
+        assert_eq!(event.amount, 1_000);
+        assert_eq!(event.received, 800);
+        assert_eq!(event.penalty, 200);
+        assert_eq!(event.rewards, 50);
+        assert_eq!(event.penalty_share, 10);
+        assert_eq!(event.remaining, 0);
+        assert_eq!(event.pool_total_staked, 9_000);
+        assert_eq!(event.total_penalty_pool, 200);
+        assert_eq!(event.unpaid_rewards, 25);
+
+        // 8 discriminator + pubkey + 10 x u64
+        assert_eq!(event.data().len(), 8 + 32 + 80);
+    }
+
+    #[test]
+    fn test_authority_events() {
+        let authority = Pubkey::new_unique();
+        let pending_authority = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(AuthorityProposed {
+                authority,
+                pending_authority,
+            });
+            emit!(AuthorityTransferred {
+                old_authority: authority,
+                new_authority: pending_authority,
+            });
+        });
+
+        let proposed: AuthorityProposed = decode_event(&logs).expect("AuthorityProposed not found in logs");
+        assert_eq!(proposed.authority, authority);
+        assert_eq!(proposed.pending_authority, pending_authority);
+
+        let transferred: AuthorityTransferred = decode_event(&logs).expect("AuthorityTransferred not found in logs");
+        assert_eq!(transferred.old_authority, authority);
+        assert_eq!(transferred.new_authority, pending_authority);
+    }
+
+    #[test]
+    fn test_pool_initialized_event() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let pool_token_account = Pubkey::new_unique();
+        let reward_vault = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(PoolInitialized {
+                authority,
+                mint,
+                pool_token_account,
+                reward_vault,
+                reward_mode: RewardMode::RewardRate,
+            });
+        });
+
+        let event: PoolInitialized = decode_event(&logs).expect("PoolInitialized not found in logs");
+        assert_eq!(event.authority, authority);
+        assert_eq!(event.mint, mint);
+        assert_eq!(event.pool_token_account, pool_token_account);
+        assert_eq!(event.reward_vault, reward_vault);
+        assert_eq!(event.reward_mode, RewardMode::RewardRate);
+    }
+
+    #[test]
+    fn test_reward_period_started_event() {
+        let logs = capture_logs(|| {
+            emit!(RewardPeriodStarted {
+                amount: 8_640_000,
+                reward_rate: 100,
+                period_end: 1_700_086_400,
+            });
+        });
+
+        let event: RewardPeriodStarted = decode_event(&logs).expect("RewardPeriodStarted not found in logs");
+        assert_eq!(event.amount, 8_640_000);
+        assert_eq!(event.reward_rate, 100);
+        assert_eq!(event.period_end, 1_700_086_400);
+    }
+
+    #[test]
+    fn test_compound_events() {
+        let owner = Pubkey::new_unique();
+        let cranker = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(AutoCompoundSet {
+                owner,
+                position_index: 3,
+                enabled: true,
+            });
+            emit!(Compounded {
+                owner,
+                position_index: 3,
+                amount: 995,
+                bounty: 5,
+                cranker,
+                position_amount: 10_995,
+                tier: Tier::Staker,
+            });
+        });
+
+        let event: AutoCompoundSet = decode_event(&logs).expect("AutoCompoundSet not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.position_index, 3);
+        assert!(event.enabled);
+
+        let event: Compounded = decode_event(&logs).expect("Compounded not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.position_index, 3);
+        assert_eq!(event.amount, 995);
+        assert_eq!(event.bounty, 5);
+        assert_eq!(event.cranker, cranker);
+        assert_eq!(event.position_amount, 10_995);
+        assert_eq!(event.tier, Tier::Staker);
+    }
+
+    #[test]
+    fn test_config_update_events() {
+        let lock_options = vec![LockOption {
+            duration: 7 * 86400,
+            apy_basis_points: 1000,
+            weight: 5_000,
+        }];
+
+        let logs = capture_logs(|| {
+            emit!(ConfigUpdateQueued {
+                lock_options: lock_options.clone(),
+                staker_threshold: 5_000_000_000_000,
+                whale_threshold: 50_000_000_000_000,
+                tier_multipliers: [1, 3, 6],
+                cooldown_period: 3 * 86400,
+                eta: 1_700_172_800,
+            });
+            emit!(ConfigUpdateCancelled { eta: 1_700_172_800 });
+            emit!(ConfigUpdated {
+                lock_options: lock_options.clone(),
+                staker_threshold: 5_000_000_000_000,
+                whale_threshold: 50_000_000_000_000,
+                tier_multipliers: [1, 3, 6],
+                cooldown_period: 3 * 86400,
+            });
+            emit!(TimelockDelayUpdated {
+                old_timelock_delay: 48 * 3600,
+                new_timelock_delay: 72 * 3600,
+                effective_at: 1_700_000_000,
+            });
+        });
+
+        let event: ConfigUpdateQueued = decode_event(&logs).expect("ConfigUpdateQueued not found in logs");
+        assert_eq!(event.lock_options, lock_options);
+        assert_eq!(event.staker_threshold, 5_000_000_000_000);
+        assert_eq!(event.whale_threshold, 50_000_000_000_000);
+        assert_eq!(event.tier_multipliers, [1, 3, 6]);
+        assert_eq!(event.cooldown_period, 3 * 86400);
+        assert_eq!(event.eta, 1_700_172_800);
+
+        let event: ConfigUpdateCancelled = decode_event(&logs).expect("ConfigUpdateCancelled not found in logs");
+        assert_eq!(event.eta, 1_700_172_800);
+
+        let event: ConfigUpdated = decode_event(&logs).expect("ConfigUpdated not found in logs");
+        assert_eq!(event.lock_options, lock_options);
+        assert_eq!(event.tier_multipliers, [1, 3, 6]);
+        assert_eq!(event.cooldown_period, 3 * 86400);
+
+        let event: TimelockDelayUpdated = decode_event(&logs).expect("TimelockDelayUpdated not found in logs");
+        assert_eq!(event.old_timelock_delay, 48 * 3600);
+        assert_eq!(event.new_timelock_delay, 72 * 3600);
+        assert_eq!(event.effective_at, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_cooldown_events() {
+        let owner = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(UnstakeRequested {
+                owner,
+                position_index: 2,
+                amount: 5_000,
+                cooldown_end: 1_700_604_800,
+                rewards: 42,
+                penalty_share: 7,
+                unpaid_rewards: 0,
+            });
+            emit!(Withdrawn {
+                owner,
+                position_index: 2,
+                amount: 5_000,
+            });
+        });
+
+        let event: UnstakeRequested = decode_event(&logs).expect("UnstakeRequested not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.position_index, 2);
+        assert_eq!(event.amount, 5_000);
+        assert_eq!(event.cooldown_end, 1_700_604_800);
+        assert_eq!(event.rewards, 42);
+        assert_eq!(event.penalty_share, 7);
+
+        let event: Withdrawn = decode_event(&logs).expect("Withdrawn not found in logs");
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.position_index, 2);
+        assert_eq!(event.amount, 5_000);
+    }
+
+    #[test]
+    fn test_rewards_funded_event() {
+        let funder = Pubkey::new_unique();
+
+        let logs = capture_logs(|| {
+            emit!(RewardsFunded {
+                funder,
+                amount: 50_000,
+                vault_balance: 80_000,
+                reward_liabilities: 12_000,
+                total_reward_funded: 150_000,
+            });
+        });
+
+        let event: RewardsFunded = decode_event(&logs).expect("RewardsFunded not found in logs");
+        assert_eq!(event.funder, funder);
+        assert_eq!(event.amount, 50_000);
+        assert_eq!(event.vault_balance, 80_000);
+        assert_eq!(event.reward_liabilities, 12_000);
+        assert_eq!(event.total_reward_funded, 150_000);
+    }
+}
@@ -2,6 +2,104 @@
 // Tests staking mechanics, reward calculations, and access control
 
 use anchor_lang::prelude::*;
+use staking::{
+    accrue_position, add_to_position, apy_rate, compound_bounty, early_withdrawal_penalty, fixed_apy_emission, remove_from_position,
+    has_stake_weight, reweight_positions, stake_weight, to_base_units,
+    validate_config, LockOption, Pool, PoolConfig, PositionStatus, RewardMode, StakeAccount, StakerSummary, Tier,
+    REWARD_PRECISION, REWARD_WEIGHT_FLOOR,
+};
+
+fn pool(total_staked: u64) -> Pool {
+    Pool {
+        authority: Pubkey::new_unique(),
+        reward_vault: Pubkey::new_unique(),
+        total_staked,
+        total_stakers: 0,
+        total_penalty_pool: 0,
+        pending_authority: Pubkey::default(),
+        mint: Pubkey::new_unique(),
+        pool_token_account: Pubkey::new_unique(),
+        acc_penalty_per_share: 0,
+        undistributed_penalty: 0,
+        reward_mode: RewardMode::FixedApy,
+        reward_rate: 0,
+        reward_period_end: 0,
+        last_update_time: 0,
+        acc_reward_per_weight: 0,
+        total_weight: 0,
+        timelock_delay: staking::DEFAULT_TIMELOCK_DELAY,
+        total_apy_rate: 0,
+        reward_liabilities: 0,
+        total_reward_funded: 0,
+        pending_timelock_delay: 0,
+        timelock_delay_eta: 0,
+    }
+}
+
+/// Reward-rate pool emitting `reward_rate` per second from t=0 to `period_end`
+fn reward_rate_pool(reward_rate: u64, period_end: i64) -> Pool {
+    let mut pool = pool(0);
+    pool.reward_mode = RewardMode::RewardRate;
+    pool.reward_rate = reward_rate;
+    pool.reward_period_end = period_end;
+    pool
+}
+
+/// Add a position with the given weight to a reward-rate pool at `now`
+fn join(pool: &mut Pool, weight: u128, now: i64) -> StakeAccount {
+    pool.update_rewards(now).unwrap();
+    let mut stake_account = staker(0, pool);
+    stake_account.weight = weight;
+    stake_account.reset_reward_debt(pool.acc_reward_per_weight);
+    pool.total_weight += weight;
+    stake_account
+}
+
+/// Default config for a 9-decimal mint
+fn config() -> PoolConfig {
+    PoolConfig::with_defaults(Pubkey::new_unique(), 9).unwrap()
+}
+
+fn summary() -> StakerSummary {
+    StakerSummary {
+        owner: Pubkey::new_unique(),
+        total_amount: 0,
+        open_positions: 0,
+        next_position_index: 0,
+        tier: Tier::None,
+    }
+}
+
+/// Staker joining the pool now
+fn staker(amount: u64, pool: &Pool) -> StakeAccount {
+    let mut stake_account = StakeAccount {
+        owner: Pubkey::new_unique(),
+        position_index: 0,
+        amount,
+        start_time: 0,
+        lock_until: 0,
+        lock_duration: 0,
+        last_claim: 0,
+        total_claimed: 0,
+        apy_basis_points: 0,
+        lock_weight: 0,
+        penalty_debt: 0,
+        pending_penalty: 0,
+        weight: 0,
+        reward_per_weight_paid: 0,
+        pending_rewards: 0,
+        auto_compound: false,
+        status: PositionStatus::Active,
+        cooling_amount: 0,
+        cooldown_end: 0,
+        apy_rate: 0,
+        unpaid_rewards: 0,
+        last_accrual: 0,
+        tier_multipliers: [1, 2, 5],
+    };
+    stake_account.reset_penalty_debt(pool.acc_penalty_per_share);
+    stake_account
+}
 
 #[cfg(test)]
 mod tests {
@@ -43,7 +141,7 @@ mod tests {
     fn test_reward_calculation_daily() {
         // Tests that daily rewards are correctly calculated
         let staked_amount = 1_000_000u64;
-        let annual_rate_bp = 2000u32; // 20% APY
+        let annual_rate_bp = 2000u64; // 20% APY
         let daily_rate_bp = annual_rate_bp / 365;
         let daily_reward = (staked_amount as u128 * daily_rate_bp as u128 / 10_000) as u64;
         
@@ -112,6 +210,42 @@ mod tests {
         assert_eq!(received_amount, 900_000);
     }
 
+    #[test]
+    fn test_early_withdrawal_penalty_curve() {
+        let lock_30 = 30 * 24 * 3600;
+        let start = 1_700_000_000;
+
+        // Full 20% at lock start (and before it, for a skewed clock)
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start, 2000), 200_000);
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start - 60, 2000), 200_000);
+
+        // Linear in between
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start + lock_30 / 4, 2000), 150_000);
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start + lock_30 / 2, 2000), 100_000);
+
+        // One second before unlock is almost free, unlock and after are free
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start + lock_30 - 1, 2000), 0);
+        assert_eq!(early_withdrawal_penalty(100_000_000_000, start, lock_30, start + lock_30 - 1, 2000), 7_716);
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start + lock_30, 2000), 0);
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, lock_30, start + 2 * lock_30, 2000), 0);
+
+        // No lock, no penalty
+        assert_eq!(early_withdrawal_penalty(1_000_000, start, 0, start, 2000), 0);
+    }
+
+    #[test]
+    fn test_position_penalty_quote() {
+        let lock_90 = 90 * 24 * 3600;
+        let mut position = staker(10_000, &pool(0));
+        position.start_time = 0;
+        position.lock_duration = lock_90;
+        position.lock_until = lock_90;
+
+        assert_eq!(position.early_withdrawal_penalty(10_000, 0), 2_000);
+        assert_eq!(position.early_withdrawal_penalty(5_000, lock_90 / 2), 500);
+        assert_eq!(position.early_withdrawal_penalty(10_000, lock_90), 0);
+    }
+
     #[test]
     fn test_lock_period_expiry() {
         // Tests that locked stake becomes unstakeable after lock period
@@ -132,6 +266,70 @@ mod tests {
         assert!(!staker.is_empty());
     }
 
+    #[test]
+    fn test_compound_keeps_lock_and_upgrades_tier() {
+        let tokens = 10u64.pow(9);
+        let lock_365 = 365 * 24 * 3600;
+        let config = config();
+        let mut pool = reward_rate_pool(10, 100);
+        let mut summary = summary();
+        summary.add_position(9_950 * tokens, &config);
+
+        let mut position = staker(9_950 * tokens, &pool);
+        position.lock_duration = lock_365;
+        position.lock_until = lock_365;
+        position.lock_weight = 75_000;
+        position.weight = stake_weight(position.amount, 75_000, 1);
+        pool.total_staked = position.amount;
+        pool.total_weight = position.weight;
+
+        // 50 tokens of rewards push the wallet over the Staker threshold
+        add_to_position(&mut pool, &config, &mut summary, &mut position, 50 * tokens, 0).unwrap();
+
+        assert_eq!(position.amount, 10_000 * tokens);
+        assert_eq!(position.lock_until, lock_365);
+        assert_eq!(summary.tier, Tier::Staker);
+        assert_eq!(position.weight, stake_weight(10_000 * tokens, 75_000, 2));
+        assert_eq!(pool.total_weight, position.weight);
+        assert_eq!(pool.total_staked, 10_000 * tokens);
+    }
+
+    #[test]
+    fn test_partial_unstake_keeps_position_open() {
+        let tokens = 10u64.pow(9);
+        let config = config();
+        let mut pool = pool(0);
+        let mut summary = summary();
+        summary.add_position(12_000 * tokens, &config);
+
+        let mut position = staker(12_000 * tokens, &pool);
+        position.lock_duration = 90 * 24 * 3600;
+        position.lock_weight = 20_000;
+        position.weight = stake_weight(position.amount, 20_000, 2);
+        pool.total_staked = position.amount;
+        pool.total_weight = position.weight;
+
+        // Dropping under 10k loses the Staker tier but not the position
+        remove_from_position(&mut pool, &config, &mut summary, &mut position, 3_000 * tokens, 0).unwrap();
+        assert_eq!(position.amount, 9_000 * tokens);
+        assert_eq!(summary.tier, Tier::None);
+        assert_eq!(summary.open_positions, 1);
+        assert_eq!(pool.total_weight, stake_weight(9_000 * tokens, 20_000, 1));
+
+        remove_from_position(&mut pool, &config, &mut summary, &mut position, 9_000 * tokens, 0).unwrap();
+        assert_eq!(summary.open_positions, 0);
+        assert_eq!(summary.total_amount, 0);
+        assert_eq!(pool.total_staked, 0);
+        assert_eq!(pool.total_weight, 0);
+    }
+
+    #[test]
+    fn test_compound_bounty() {
+        // 0.5% of the compounded rewards
+        assert_eq!(compound_bounty(10_000), 50);
+        assert_eq!(compound_bounty(199), 0);
+    }
+
     #[test]
     fn test_restake_rewards() {
         // Tests that rewards can be restaked
@@ -152,6 +350,39 @@ mod tests {
         assert_eq!(total, 800_000);
     }
 
+    #[test]
+    fn test_tier_from_sum_of_positions() {
+        let tokens = 10u64.pow(9);
+        let config = config();
+        let mut summary = summary();
+
+        // Neither position reaches a tier on its own
+        assert_eq!(summary.add_position(6_000 * tokens, &config), 0);
+        assert_eq!(summary.tier, Tier::None);
+        assert_eq!(summary.add_position(6_000 * tokens, &config), 1);
+        assert_eq!(summary.tier, Tier::Staker);
+        assert_eq!(summary.total_amount, 12_000 * tokens);
+
+        summary.remove_position(6_000 * tokens, &config);
+        assert_eq!(summary.tier, Tier::None);
+        assert_eq!(summary.open_positions, 1);
+
+        assert_eq!(config.tier_for_amount(100_000 * tokens), Tier::Whale);
+    }
+
+    #[test]
+    fn test_position_indexes_never_reused() {
+        let config = config();
+        let mut summary = summary();
+        summary.add_position(100, &config);
+        summary.add_position(100, &config);
+
+        // Closing position #1 doesn't free its index (or seeds) for the next one
+        summary.remove_position(100, &config);
+        assert_eq!(summary.add_position(100, &config), 2);
+        assert_eq!(summary.open_positions, 2);
+    }
+
     #[test]
     fn test_reward_pool_availability() {
         // Tests that reward pool has sufficient funds
@@ -222,4 +453,491 @@ mod tests {
         assert_eq!(staker_1_share, 60_000);
         assert_eq!(staker_2_share, 40_000);
     }
+
+    #[test]
+    fn test_penalty_distributed_pro_rata() {
+        // 1_000 left staked after the early leaver's stake was removed
+        let mut pool = pool(1_000);
+        let alice = staker(600, &pool);
+        let bob = staker(400, &pool);
+
+        pool.distribute_penalty(100);
+
+        assert_eq!(alice.accrued_penalty_share(pool.acc_penalty_per_share), 60);
+        assert_eq!(bob.accrued_penalty_share(pool.acc_penalty_per_share), 40);
+        assert_eq!(pool.total_penalty_pool, 100);
+    }
+
+    #[test]
+    fn test_penalty_rounding_dust_stays_in_pool() {
+        let mut pool = pool(3);
+        let stakers: Vec<StakeAccount> = (0..3).map(|_| staker(1, &pool)).collect();
+
+        pool.distribute_penalty(10);
+
+        let credited: u64 = stakers.iter().map(|s| s.accrued_penalty_share(pool.acc_penalty_per_share)).sum();
+        assert_eq!(credited, 9);
+        assert_eq!(pool.total_penalty_pool, 10);
+    }
+
+    #[test]
+    fn test_penalty_share_as_stakers_join_and_leave() {
+        let mut pool = pool(1_000);
+        let mut alice = staker(1_000, &pool);
+
+        pool.distribute_penalty(100);
+
+        // Bob joins afterwards and gets nothing from the first penalty
+        let bob = staker(1_000, &pool);
+        pool.total_staked += 1_000;
+        pool.distribute_penalty(200);
+
+        assert_eq!(alice.accrued_penalty_share(pool.acc_penalty_per_share), 200);
+        assert_eq!(bob.accrued_penalty_share(pool.acc_penalty_per_share), 100);
+
+        // Alice adds to her stake: what she earned so far is settled, not lost
+        alice.settle_penalty_share(pool.acc_penalty_per_share);
+        alice.amount += 1_000;
+        alice.reset_penalty_debt(pool.acc_penalty_per_share);
+        pool.total_staked += 1_000;
+        assert_eq!(alice.pending_penalty, 200);
+        assert_eq!(alice.accrued_penalty_share(pool.acc_penalty_per_share), 0);
+
+        // Bob leaves; his settled share stays claimable
+        let mut bob = bob;
+        bob.settle_penalty_share(pool.acc_penalty_per_share);
+        bob.amount = 0;
+        bob.reset_penalty_debt(pool.acc_penalty_per_share);
+        pool.total_staked -= 1_000;
+        pool.distribute_penalty(300);
+
+        assert_eq!(bob.pending_penalty, 100);
+        assert_eq!(bob.accrued_penalty_share(pool.acc_penalty_per_share), 0);
+        assert_eq!(alice.accrued_penalty_share(pool.acc_penalty_per_share), 300);
+    }
+
+    #[test]
+    fn test_penalty_carried_over_when_nobody_staked() {
+        // Last staker left early: nobody to credit yet
+        let mut pool = pool(0);
+        pool.distribute_penalty(50);
+        assert_eq!(pool.acc_penalty_per_share, 0);
+        assert_eq!(pool.undistributed_penalty, 50);
+
+        pool.total_staked = 500;
+        let carol = staker(500, &pool);
+        pool.distribute_penalty(50);
+
+        assert_eq!(carol.accrued_penalty_share(pool.acc_penalty_per_share), 100);
+        assert_eq!(pool.undistributed_penalty, 0);
+        assert_eq!(pool.total_penalty_pool, 100);
+    }
+
+    #[test]
+    fn test_stake_weight() {
+        let amount = 1_000u64;
+        let config = config();
+        let lock_30 = config.lock_option(30 * 24 * 3600).unwrap().weight;
+        let lock_365 = config.lock_option(365 * 24 * 3600).unwrap().weight;
+        let whale = config.tier_multiplier(&Tier::Whale);
+
+        assert_eq!(stake_weight(amount, lock_30, 1), 1_000);
+        assert_eq!(stake_weight(amount, lock_365, 1), 7_500);
+        assert_eq!(stake_weight(amount, lock_365, whale), 37_500);
+        assert_eq!(config.tier_multiplier(&Tier::Staker), 2);
+
+        // Durations the pool doesn't offer can't be opened
+        assert!(config.lock_option(12345).is_none());
+    }
+
+    #[test]
+    fn test_default_config_scales_thresholds_by_decimals() {
+        let config_9 = PoolConfig::with_defaults(Pubkey::new_unique(), 9).unwrap();
+        let config_6 = PoolConfig::with_defaults(Pubkey::new_unique(), 6).unwrap();
+
+        assert_eq!(config_9.staker_threshold, 10_000_000_000_000);
+        assert_eq!(config_6.staker_threshold, 10_000_000_000);
+        assert_eq!(config_6.whale_threshold, 100_000_000_000);
+        assert_eq!(config_6.tier_for_amount(10_000_000_000), Tier::Staker);
+        assert_eq!(config_9.tier_for_amount(10_000_000_000), Tier::None);
+        assert_eq!(config_9.lock_options.len(), 5);
+        assert!(config_9.lock_option(0).is_some());
+
+        // 100k whole tokens don't fit in a u64 at 18 decimals
+        assert!(PoolConfig::with_defaults(Pubkey::new_unique(), 18).is_none());
+        assert_eq!(to_base_units(5, 2), Some(500));
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let option = |duration: i64| LockOption {
+            duration,
+            apy_basis_points: 1000,
+            weight: 10_000,
+        };
+        let options = vec![option(7 * 86400), option(30 * 86400)];
+
+        assert!(validate_config(&options, 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+
+        // Lock options: at least one, no duplicates, no negative durations or zero weights
+        assert!(!validate_config(&[], 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+        assert!(!validate_config(&[option(86400), option(86400)], 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+        assert!(!validate_config(&[option(-1)], 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+        let mut weightless = option(86400);
+        weightless.weight = 0;
+        assert!(!validate_config(&[weightless], 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+        let too_many: Vec<LockOption> = (1..=9).map(|d| option(d * 86400)).collect();
+        assert!(!validate_config(&too_many, 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+
+        // Thresholds ordered, multipliers at least 1x and non-decreasing
+        assert!(!validate_config(&options, 10_000, 10_000, &[1, 2, 5], 7 * 86400));
+        assert!(!validate_config(&options, 0, 10_000, &[1, 2, 5], 7 * 86400));
+        assert!(!validate_config(&options, 1_000, 10_000, &[0, 2, 5], 7 * 86400));
+        assert!(!validate_config(&options, 1_000, 10_000, &[1, 5, 2], 7 * 86400));
+
+        // A flexible option is fine, the cooldown must be positive and bounded
+        assert!(validate_config(&[option(0)], 1_000, 10_000, &[1, 2, 5], 7 * 86400));
+        assert!(!validate_config(&options, 1_000, 10_000, &[1, 2, 5], 0));
+        assert!(!validate_config(&options, 1_000, 10_000, &[1, 2, 5], 31 * 86400));
+    }
+
+    #[test]
+    fn test_flexible_position_status() {
+        let pool = pool(1_000);
+        let mut position = staker(1_000, &pool);
+        assert!(position.is_flexible());
+        assert_eq!(position.status_at(0), PositionStatus::Active);
+        assert_eq!(position.early_withdrawal_penalty(1_000, 0), 0);
+
+        // request_unstake at t=100 with a 7-day cooldown
+        position.status = PositionStatus::Cooling;
+        position.cooling_amount = 1_000;
+        position.amount = 0;
+        position.cooldown_end = 100 + 7 * 86400;
+
+        assert_eq!(position.status_at(100), PositionStatus::Cooling);
+        assert_eq!(position.status_at(position.cooldown_end - 1), PositionStatus::Cooling);
+        assert_eq!(position.status_at(position.cooldown_end), PositionStatus::Withdrawable);
+
+        // Locked positions never cool down
+        position.lock_duration = 30 * 86400;
+        assert!(!position.is_flexible());
+    }
+
+    #[test]
+    fn test_reward_rate_split_by_weight() {
+        // 10 tokens/s for 100s = 1_000 tokens in total
+        let mut pool = reward_rate_pool(10, 100);
+        let alice = join(&mut pool, 3_000, 0);
+        let bob = join(&mut pool, 1_000, 0);
+
+        pool.update_rewards(100).unwrap();
+
+        assert_eq!(alice.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 750);
+        assert_eq!(bob.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 250);
+    }
+
+    #[test]
+    fn test_reward_rate_bounded_by_period() {
+        // Nothing is emitted past the end of the funded period
+        let mut pool = reward_rate_pool(10, 100);
+        let alice = join(&mut pool, 1_000, 0);
+
+        pool.update_rewards(1_000_000).unwrap();
+
+        assert_eq!(alice.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 1_000);
+        assert_eq!(pool.last_update_time, 100);
+    }
+
+    #[test]
+    fn test_reward_rate_late_joiner() {
+        let mut pool = reward_rate_pool(10, 100);
+        let mut alice = join(&mut pool, 1_000, 0);
+
+        // Bob joins halfway: the first 500 tokens are all Alice's
+        let bob = join(&mut pool, 1_000, 50);
+        pool.update_rewards(100).unwrap();
+
+        assert_eq!(alice.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 750);
+        assert_eq!(bob.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 250);
+
+        // Settling keeps what was earned
+        alice.settle_rewards(pool.acc_reward_per_weight).unwrap();
+        assert_eq!(alice.pending_rewards, 750);
+        assert_eq!(alice.accrued_rewards(pool.acc_reward_per_weight).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fixed_apy_pool_accrues_nothing() {
+        let mut pool = pool(0);
+        pool.total_weight = 1_000;
+
+        pool.update_rewards(1_000_000).unwrap();
+
+        assert_eq!(pool.acc_reward_per_weight, 0);
+    }
+
+    #[test]
+    fn test_reward_rate_liabilities_and_runway() {
+        let mut pool = reward_rate_pool(10, 100);
+        let _alice = join(&mut pool, 1_000, 0);
+
+        // Emitted-but-unpaid rewards are owed to stakers
+        pool.update_rewards(40).unwrap();
+        assert_eq!(pool.reward_liabilities, 400);
+
+        // 1_000 in the vault, 400 of it owed: 600 left at 10/s
+        assert_eq!(pool.free_rewards(1_000), 600);
+        assert_eq!(pool.reward_runway(1_000, 40), 60);
+        assert_eq!(pool.reward_runway(300, 40), 0);
+
+        // Nothing is emitted after the period
+        pool.update_rewards(200).unwrap();
+        assert_eq!(pool.reward_liabilities, 1_000);
+        assert_eq!(pool.reward_runway(1_000, 200), u64::MAX);
+    }
+
+    #[test]
+    fn test_dust_staker_then_large_staker() {
+        // 1e18 emitted over 30 days with a 1-unit position staked on its own for an hour
+        let period = 30 * 86400;
+        let mut pool = reward_rate_pool(1_000_000_000_000_000_000 / period as u64, period);
+        let mut dust = join(&mut pool, 1, 0);
+        pool.update_rewards(3600).unwrap();
+
+        // Credited as a share of the floor weight, not the whole emission
+        let emitted = 3600 * pool.reward_rate as u128;
+        assert_eq!(pool.acc_reward_per_weight, emitted * REWARD_PRECISION / REWARD_WEIGHT_FLOOR);
+        assert_eq!(pool.reward_liabilities as u128, emitted.div_ceil(REWARD_WEIGHT_FLOOR));
+
+        // A billion-token position at 7.5x whale weight joins and stays to the end
+        let tokens = 10u64.pow(9);
+        let mut whale = join(&mut pool, stake_weight(1_000_000_000 * tokens, 75_000, 5), 3600);
+        accrue_position(&mut pool, &mut whale, period).unwrap();
+        accrue_position(&mut pool, &mut dust, period).unwrap();
+
+        // Nearly all of the rest is the whale's, less weight / REWARD_PRECISION of rounding
+        let remaining = (period - 3600) as u128 * pool.reward_rate as u128;
+        let rounding = whale.weight / REWARD_PRECISION + 1;
+        assert!(whale.pending_rewards as u128 <= remaining && whale.pending_rewards as u128 >= remaining - rounding);
+        assert!(whale.pending_rewards + dust.pending_rewards <= pool.reward_liabilities);
+
+        // Weights no position can reach fail instead of wrapping
+        whale.weight = u128::MAX / 2;
+        assert!(whale.accrued_rewards(pool.acc_reward_per_weight + 2).is_err());
+        let mut overflowing = reward_rate_pool(u64::MAX, i64::MAX);
+        overflowing.total_weight = 1;
+        overflowing.acc_reward_per_weight = u128::MAX - 1;
+        assert!(overflowing.update_rewards(1).is_err());
+    }
+
+    #[test]
+    fn test_zero_weight_positions_rejected() {
+        // Weight is rounded down to basis points of the lock weight
+        assert!(!has_stake_weight(1, 5_000));
+        assert!(has_stake_weight(2, 5_000));
+        assert!(!has_stake_weight(9_999, 1));
+        assert!(has_stake_weight(10_000, 1));
+        assert!(!has_stake_weight(0, 75_000));
+    }
+
+    #[test]
+    fn test_fixed_apy_liabilities_and_runway() {
+        let mut pool = pool(1_000_000);
+        let year = 365 * 86400;
+
+        // 1M tokens at 20% APY, whale multiplier: 1M a year
+        pool.total_apy_rate = apy_rate(1_000_000, 2000, 5);
+        assert_eq!(fixed_apy_emission(pool.total_apy_rate, year), 1_000_000);
+
+        pool.update_rewards(year / 4).unwrap();
+        assert_eq!(pool.reward_liabilities, 250_000);
+
+        // Half a year's worth left after what is already owed
+        assert_eq!(pool.reward_runway(750_000, year / 4), (year / 2) as u64);
+
+        pool.total_apy_rate = 0;
+        assert_eq!(pool.reward_runway(0, year / 4), u64::MAX);
+    }
+
+    #[test]
+    fn test_fixed_apy_accrues_at_position_rate() {
+        let tokens = 10u64.pow(9);
+        let year = 365 * 86400;
+        let config = config();
+        let mut pool = pool(0);
+        let mut summary = summary();
+        summary.add_position(9_000 * tokens, &config);
+
+        let mut position = staker(9_000 * tokens, &pool);
+        position.apy_basis_points = 2000;
+        position.apy_rate = apy_rate(position.amount, 2000, 1);
+        pool.total_staked = position.amount;
+        pool.total_apy_rate = position.apy_rate;
+
+        // Half a year at 1x, then a top-up reaches the Staker tier
+        add_to_position(&mut pool, &config, &mut summary, &mut position, 1_000 * tokens, year / 2).unwrap();
+        assert_eq!(summary.tier, Tier::Staker);
+        assert_eq!(position.pending_rewards, 900 * tokens);
+        assert_eq!(position.apy_rate, apy_rate(10_000 * tokens, 2000, 2));
+
+        // The 2x rate only applies from the top-up on
+        accrue_position(&mut pool, &mut position, year).unwrap();
+        assert_eq!(position.pending_rewards, 900 * tokens + 2_000 * tokens);
+        assert_eq!(position.last_accrual, year);
+    }
+
+    #[test]
+    fn test_tier_drop_reweights_other_positions() {
+        let tokens = 10u64.pow(9);
+        let config = config();
+        let mut pool = reward_rate_pool(10, 1_000);
+        let mut summary = summary();
+
+        // A locked position weighted while a large flexible one made the wallet a Whale
+        summary.add_position(5_000 * tokens, &config);
+        summary.add_position(100_000 * tokens, &config);
+        assert_eq!(summary.tier, Tier::Whale);
+        let mut locked = staker(5_000 * tokens, &pool);
+        locked.owner = summary.owner;
+        locked.lock_weight = 75_000;
+        locked.weight = stake_weight(locked.amount, 75_000, 5);
+        let mut flexible = staker(100_000 * tokens, &pool);
+        flexible.owner = summary.owner;
+        flexible.lock_weight = 5_000;
+        flexible.weight = stake_weight(flexible.amount, 5_000, 5);
+        pool.total_staked = 105_000 * tokens;
+        pool.total_weight = locked.weight + flexible.weight;
+
+        // Leaving with the flexible position drops the tier
+        remove_from_position(&mut pool, &config, &mut summary, &mut flexible, 100_000 * tokens, 100).unwrap();
+        assert_eq!(summary.tier, Tier::None);
+
+        let flexible_key = Pubkey::new_unique();
+        let locked_key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = Vec::new();
+        locked.try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(&locked_key, false, true, &mut lamports, &mut data, &staking::ID, false, 0);
+
+        // Every other open position has to be passed
+        let missing = reweight_positions(&mut pool, &summary, &flexible_key, false, &[], 100);
+        assert!(missing.is_err());
+
+        reweight_positions(&mut pool, &summary, &flexible_key, false, std::slice::from_ref(&info), 100).unwrap();
+        let reweighted = StakeAccount::try_deserialize(&mut &info.data.borrow()[..]).unwrap();
+        assert_eq!(reweighted.weight, stake_weight(5_000 * tokens, 75_000, 1));
+        assert_eq!(pool.total_weight, reweighted.weight);
+
+        // What it earned as a Whale's position was settled first, at the old weight
+        assert!(reweighted.pending_rewards > 0);
+        assert_eq!(reweighted.pending_rewards, locked.accrued_rewards(pool.acc_reward_per_weight).unwrap());
+    }
+
+    #[test]
+    fn test_position_keeps_tier_multipliers_it_was_opened_with() {
+        let tokens = 10u64.pow(9);
+        let mut config = config();
+        let mut pool = pool(0);
+        let mut summary = summary();
+        summary.add_position(9_000 * tokens, &config);
+
+        let mut position = staker(9_000 * tokens, &pool);
+        position.apy_basis_points = 2000;
+        position.apy_rate = apy_rate(position.amount, 2000, 1);
+        pool.total_staked = position.amount;
+        pool.total_apy_rate = position.apy_rate;
+
+        // A config update raising the Staker multiplier doesn't reach open positions
+        config.tier_multipliers = [1, 3, 6];
+        add_to_position(&mut pool, &config, &mut summary, &mut position, 1_000 * tokens, 0).unwrap();
+        assert_eq!(summary.tier, Tier::Staker);
+        assert_eq!(position.tier_multiplier(&summary.tier), 2);
+        assert_eq!(position.apy_rate, apy_rate(10_000 * tokens, 2000, 2));
+        assert_eq!(pool.total_apy_rate, position.apy_rate);
+    }
+
+    #[test]
+    fn test_payouts_never_exceed_liabilities() {
+        // Three equal weights splitting 10 per second round down for each of them
+        let mut reward_pool = reward_rate_pool(10, 100);
+        let mut stakers: Vec<StakeAccount> = (0..3).map(|_| join(&mut reward_pool, 1_000, 0)).collect();
+        for now in [7, 20, 33, 100] {
+            for stake_account in stakers.iter_mut() {
+                accrue_position(&mut reward_pool, stake_account, now).unwrap();
+            }
+        }
+        let paid: u64 = stakers.iter().map(|stake_account| stake_account.pending_rewards).sum();
+        assert_eq!(reward_pool.reward_liabilities, 1_000);
+        // At most one unit of rounding dust per settlement stays with the pool
+        assert!(paid <= reward_pool.reward_liabilities && paid >= 1_000 - 3 * 4);
+
+        // Fixed APY positions accruing at different times than the pool
+        let mut apy_pool = pool(0);
+        let mut stakers: Vec<StakeAccount> = (1..4u64)
+            .map(|i| {
+                let mut stake_account = staker(1_000 * i, &apy_pool);
+                stake_account.apy_rate = apy_rate(stake_account.amount, 1234, 1);
+                apy_pool.total_apy_rate += stake_account.apy_rate;
+                stake_account
+            })
+            .collect();
+        for now in [86_400, 86_401, 3 * 86_400 + 17, 365 * 86_400] {
+            for stake_account in stakers.iter_mut() {
+                accrue_position(&mut apy_pool, stake_account, now).unwrap();
+                apy_pool.update_rewards(now + 1).unwrap();
+            }
+        }
+        let paid: u64 = stakers.iter().map(|stake_account| stake_account.pending_rewards).sum();
+        assert!(paid <= apy_pool.reward_liabilities);
+    }
+
+    #[test]
+    fn test_position_closable_only_without_iou() {
+        let pool = pool(0);
+        let mut position = staker(0, &pool);
+        assert!(position.is_closable());
+
+        position.unpaid_rewards = 5;
+        assert!(!position.is_closable());
+
+        position.unpaid_rewards = 0;
+        position.cooling_amount = 1_000;
+        assert!(!position.is_closable());
+    }
+
+    #[test]
+    fn test_staking_interface_reads_summary_layout() {
+        // Other programs read the tier through staking-interface's mirror
+        assert_eq!(staking::ID, staking_interface::ID);
+        let owner = Pubkey::new_unique();
+        let (address, _) = Pubkey::find_program_address(&[b"staker", owner.as_ref()], &staking::ID);
+        assert_eq!(staking_interface::staker_summary_address(&owner), address);
+
+        let tiers = [
+            (Tier::None, staking_interface::Tier::None),
+            (Tier::Staker, staking_interface::Tier::Staker),
+            (Tier::Whale, staking_interface::Tier::Whale),
+        ];
+        for (tier, expected) in tiers {
+            let mut summary = summary();
+            summary.owner = owner;
+            summary.total_amount = 123_456;
+            summary.open_positions = 2;
+            summary.next_position_index = 5;
+            summary.tier = tier;
+
+            let mut data = Vec::new();
+            summary.try_serialize(&mut data).unwrap();
+            let mirror = staking_interface::StakerSummary::try_deserialize(&mut data.as_slice()).unwrap();
+
+            assert_eq!(mirror.owner, owner);
+            assert_eq!(mirror.total_amount, 123_456);
+            assert_eq!(mirror.open_positions, 2);
+            assert_eq!(mirror.next_position_index, 5);
+            assert_eq!(staking_interface::tier_of(Some(&mirror)), expected);
+        }
+
+        assert_eq!(staking_interface::tier_of(None), staking_interface::Tier::None);
+    }
 }
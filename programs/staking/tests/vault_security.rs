@@ -0,0 +1,770 @@
+// Staking Vault Security Tests
+// Runs the program under solana-program-test and checks that only the pool
+// authority PDA can move tokens out of the pool and reward vaults, and that the
+// events real instructions emit decode as the indexer expects.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use event_testing::{decode_events, program_logs, record_program_logs};
+use staking::{LockOption, Pool, PoolConfig, PositionStatus, RewardMode, StakeAccount, StakerSummary, Staked, Unstaked};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+const LOCK_30_DAYS: i64 = 30 * 24 * 3600;
+const LOCK_365_DAYS: i64 = 365 * 24 * 3600;
+const ALICE_STAKE: u64 = 100_000;
+const REWARD_FUNDING: u64 = 1_000_000;
+
+// Anchor 0.29's entrypoint ties the account slice to 'info, program-test doesn't
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    staking::entry(program_id, accounts, data)
+}
+
+struct Fixture {
+    context: ProgramTestContext,
+    mint: Pubkey,
+    pool_vault: Pubkey,
+    reward_vault: Pubkey,
+    alice: Keypair,
+    alice_tokens: Pubkey,
+    stranger: Keypair,
+    stranger_tokens: Pubkey,
+}
+
+fn pool_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool"], &staking::ID).0
+}
+
+fn pool_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_authority", pool_address().as_ref()], &staking::ID).0
+}
+
+fn pool_vault_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_vault", pool_address().as_ref()], &staking::ID).0
+}
+
+fn reward_vault_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"reward_vault", pool_address().as_ref()], &staking::ID).0
+}
+
+fn pool_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_config", pool_address().as_ref()], &staking::ID).0
+}
+
+fn pending_config_update_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pending_config_update", pool_address().as_ref()], &staking::ID).0
+}
+
+fn staker_summary_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"staker", owner.as_ref()], &staking::ID).0
+}
+
+fn stake_address(owner: &Pubkey, position_index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake", owner.as_ref(), &position_index.to_le_bytes()], &staking::ID).0
+}
+
+async fn send(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Custom error code the transaction failed with, if any
+fn error_code(result: std::result::Result<(), BanksClientError>) -> Option<u32> {
+    match result.err()?.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+async fn token_balance(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Early withdrawal penalty the owner's position 0 would pay at the current clock
+async fn quote_penalty(context: &mut ProgramTestContext, owner: &Pubkey, amount: u64) -> u64 {
+    let position: StakeAccount = fetch(context, &stake_address(owner, 0)).await;
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    position.early_withdrawal_penalty(amount, clock.unix_timestamp)
+}
+
+/// Move the clock `seconds` ahead, on a fresh slot so replayed instructions
+/// get a new blockhash
+async fn warp_forward(context: &mut ProgramTestContext, seconds: i64) {
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 100).unwrap();
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+async fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+    let account = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    send(
+        context,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::id(), &account.pubkey(), mint, owner).unwrap(),
+            spl_token::instruction::mint_to(&spl_token::id(), mint, &account.pubkey(), &payer, &[], amount).unwrap(),
+        ],
+        &[&account],
+    )
+    .await
+    .unwrap();
+    account.pubkey()
+}
+
+/// Opens the owner's first position (index 0)
+fn stake_ix(owner: &Pubkey, user_token_account: &Pubkey, amount: u64) -> Instruction {
+    open_position_ix(owner, user_token_account, amount, 0, LOCK_30_DAYS)
+}
+
+fn open_position_ix(owner: &Pubkey, user_token_account: &Pubkey, amount: u64, position_index: u64, lock_duration: i64) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::OpenPosition {
+            staker_summary: staker_summary_address(owner),
+            stake_account: stake_address(owner, position_index),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            owner: *owner,
+            user_token_account: *user_token_account,
+            pool_token_account: pool_vault_address(),
+            system_program: system_program::ID,
+            token_program: spl_token::id(),
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: staking::instruction::OpenPosition { amount, lock_duration }
+        .data(),
+    }
+}
+
+fn claim_ix(owner: &Pubkey, user_token_account: &Pubkey, reward_vault: &Pubkey, pool_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::ClaimRewards {
+            stake_account: stake_address(owner, 0),
+            staker_summary: staker_summary_address(owner),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            owner: *owner,
+            user_token_account: *user_token_account,
+            reward_vault: *reward_vault,
+            pool_token_account: pool_vault_address(),
+            pool_authority: *pool_authority,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::ClaimRewards {}.data(),
+    }
+}
+
+fn crank_compound_ix(owner: &Pubkey, cranker: &Pubkey, cranker_token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::CrankCompound {
+            stake_account: stake_address(owner, 0),
+            staker_summary: staker_summary_address(owner),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            cranker: *cranker,
+            cranker_token_account: *cranker_token_account,
+            reward_vault: reward_vault_address(),
+            pool_token_account: pool_vault_address(),
+            pool_authority: pool_authority_address(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::CrankCompound {}.data(),
+    }
+}
+
+fn unstake_partial_ix(owner: &Pubkey, user_token_account: &Pubkey, amount: u64) -> Instruction {
+    let mut ix = unstake_ix(&stake_address(owner, 0), owner, user_token_account, &pool_vault_address());
+    ix.data = staking::instruction::UnstakePartial { amount }.data();
+    ix
+}
+
+fn request_unstake_ix(owner: &Pubkey, user_token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::RequestUnstake {
+            stake_account: stake_address(owner, 0),
+            staker_summary: staker_summary_address(owner),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            owner: *owner,
+            user_token_account: *user_token_account,
+            pool_token_account: pool_vault_address(),
+            reward_vault: reward_vault_address(),
+            pool_authority: pool_authority_address(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::RequestUnstake {}.data(),
+    }
+}
+
+fn withdraw_ix(owner: &Pubkey, user_token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::Withdraw {
+            stake_account: stake_address(owner, 0),
+            pool: pool_address(),
+            owner: *owner,
+            user_token_account: *user_token_account,
+            pool_token_account: pool_vault_address(),
+            pool_authority: pool_authority_address(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::Withdraw {}.data(),
+    }
+}
+
+fn queue_config_update_ix(authority: &Pubkey, update: staking::instruction::QueueConfigUpdate) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::QueueConfigUpdate {
+            pending_config_update: pending_config_update_address(),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: update.data(),
+    }
+}
+
+fn execute_config_update_ix(queued_by: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::ExecuteConfigUpdate {
+            pending_config_update: pending_config_update_address(),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            queued_by: *queued_by,
+        }
+        .to_account_metas(None),
+        data: staking::instruction::ExecuteConfigUpdate {}.data(),
+    }
+}
+
+fn unstake_ix(stake_account: &Pubkey, owner: &Pubkey, user_token_account: &Pubkey, pool_token_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::Unstake {
+            stake_account: *stake_account,
+            staker_summary: staker_summary_address(owner),
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            owner: *owner,
+            user_token_account: *user_token_account,
+            pool_token_account: *pool_token_account,
+            reward_vault: reward_vault_address(),
+            pool_authority: pool_authority_address(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::ClosePosition {}.data(),
+    }
+}
+
+/// Pool with a funded reward vault, Alice staking 100k and a stranger holding 1k
+async fn setup() -> Fixture {
+    let program_test = ProgramTest::new("staking", staking::ID, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    send(
+        &mut context,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), &payer, None, 9).unwrap(),
+        ],
+        &[&mint],
+    )
+    .await
+    .unwrap();
+
+    let init_ix = Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::InitializePool {
+            pool: pool_address(),
+            pool_config: pool_config_address(),
+            authority: payer,
+            pool_authority: pool_authority_address(),
+            mint: mint.pubkey(),
+            pool_token_account: pool_vault_address(),
+            reward_vault: reward_vault_address(),
+            system_program: system_program::ID,
+            token_program: spl_token::id(),
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: staking::instruction::InitializePool {
+            reward_mode: RewardMode::FixedApy,
+        }
+        .data(),
+    };
+    let fund_ix = spl_token::instruction::mint_to(&spl_token::id(), &mint.pubkey(), &reward_vault_address(), &payer, &[], REWARD_FUNDING).unwrap();
+    send(&mut context, &[init_ix, fund_ix], &[]).await.unwrap();
+
+    let alice = Keypair::new();
+    let stranger = Keypair::new();
+    for wallet in [&alice, &stranger] {
+        let fund = system_instruction::transfer(&payer, &wallet.pubkey(), 1_000_000_000);
+        send(&mut context, &[fund], &[]).await.unwrap();
+    }
+    let alice_tokens = create_token_account(&mut context, &mint.pubkey(), &alice.pubkey(), ALICE_STAKE).await;
+    let stranger_tokens = create_token_account(&mut context, &mint.pubkey(), &stranger.pubkey(), 1_000).await;
+
+    send(&mut context, &[stake_ix(&alice.pubkey(), &alice_tokens, ALICE_STAKE)], &[&alice]).await.unwrap();
+
+    Fixture {
+        context,
+        mint: mint.pubkey(),
+        pool_vault: pool_vault_address(),
+        reward_vault: reward_vault_address(),
+        alice,
+        alice_tokens,
+        stranger,
+        stranger_tokens,
+    }
+}
+
+#[tokio::test]
+async fn test_vaults_owned_by_pool_authority() {
+    let mut fixture = setup().await;
+
+    for vault in [fixture.pool_vault, fixture.reward_vault] {
+        let account = fixture.context.banks_client.get_account(vault).await.unwrap().unwrap();
+        let token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(token_account.owner, pool_authority_address());
+        assert_eq!(token_account.mint, fixture.mint);
+    }
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, ALICE_STAKE);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.reward_vault).await, REWARD_FUNDING);
+}
+
+#[tokio::test]
+async fn test_stranger_cannot_transfer_from_vaults() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+
+    for vault in [fixture.pool_vault, fixture.reward_vault] {
+        let ix = spl_token::instruction::transfer(&spl_token::id(), &vault, &fixture.stranger_tokens, &stranger.pubkey(), &[], 1).unwrap();
+        assert!(send(&mut fixture.context, &[ix], &[&stranger]).await.is_err());
+    }
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, ALICE_STAKE);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.reward_vault).await, REWARD_FUNDING);
+}
+
+#[tokio::test]
+async fn test_stranger_cannot_unstake_someone_elses_position() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+    send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await.unwrap();
+
+    // Alice's position is derived from Alice, not from the signer, even at the
+    // same position index
+    let ix = unstake_ix(&stake_address(&fixture.alice.pubkey(), 0), &stranger.pubkey(), &fixture.stranger_tokens, &fixture.pool_vault);
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintSeeds as u32));
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, ALICE_STAKE + 1_000);
+}
+
+#[tokio::test]
+async fn test_substituted_vaults_rejected() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+    send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await.unwrap();
+
+    // Claiming "rewards" out of the staked principal
+    let ix = claim_ix(&stranger.pubkey(), &fixture.stranger_tokens, &fixture.pool_vault, &pool_authority_address());
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::InvalidVault)));
+
+    // Unstaking out of the reward vault
+    let ix = unstake_ix(&stake_address(&stranger.pubkey(), 0), &stranger.pubkey(), &fixture.stranger_tokens, &fixture.reward_vault);
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::InvalidVault)));
+
+    // A signer of the stranger's choosing in place of the pool authority PDA
+    let ix = claim_ix(&stranger.pubkey(), &fixture.stranger_tokens, &fixture.reward_vault, &stranger.pubkey());
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintSeeds as u32));
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, ALICE_STAKE + 1_000);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.reward_vault).await, REWARD_FUNDING);
+}
+
+#[tokio::test]
+async fn test_unstake_pays_out_only_own_stake() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+    send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await.unwrap();
+
+    // Just staked: close to the full 20% early withdrawal penalty stays in the pool
+    let penalty = quote_penalty(&mut fixture.context, &stranger.pubkey(), 1_000).await;
+    assert!(penalty > 190 && penalty <= 200);
+    let ix = unstake_ix(&stake_address(&stranger.pubkey(), 0), &stranger.pubkey(), &fixture.stranger_tokens, &fixture.pool_vault);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.stranger_tokens).await, 1_000 - penalty);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, ALICE_STAKE + penalty);
+
+    // The PDA-signed payout also works for the legitimate owner, who as the
+    // only one left staked also collects the stranger's penalty. Her own
+    // penalty waits in the pool for the next staker.
+    let alice = fixture.alice.insecure_clone();
+    let alice_penalty = quote_penalty(&mut fixture.context, &alice.pubkey(), ALICE_STAKE).await;
+    let ix = unstake_ix(&stake_address(&alice.pubkey(), 0), &alice.pubkey(), &fixture.alice_tokens, &fixture.pool_vault);
+    send(&mut fixture.context, &[ix], &[&alice]).await.unwrap();
+    let rewards = REWARD_FUNDING - token_balance(&mut fixture.context, &fixture.reward_vault).await;
+    assert_eq!(
+        token_balance(&mut fixture.context, &fixture.alice_tokens).await,
+        ALICE_STAKE - alice_penalty + penalty + rewards
+    );
+    assert_eq!(token_balance(&mut fixture.context, &fixture.pool_vault).await, alice_penalty);
+}
+
+#[tokio::test]
+async fn test_second_position_keeps_first_lock() {
+    let mut fixture = setup().await;
+    let alice = fixture.alice.insecure_clone();
+    let first = stake_address(&alice.pubkey(), 0);
+    let first_lock = fetch::<StakeAccount>(&mut fixture.context, &first).await.lock_until;
+
+    // Position #0 is 30 days; a second position must not touch it
+    let extra_tokens = create_token_account(&mut fixture.context, &fixture.mint, &alice.pubkey(), 1_000).await;
+    let ix = open_position_ix(&alice.pubkey(), &extra_tokens, 1_000, 1, LOCK_365_DAYS);
+    send(&mut fixture.context, &[ix], &[&alice]).await.unwrap();
+
+    let position_0: StakeAccount = fetch(&mut fixture.context, &first).await;
+    let position_1: StakeAccount = fetch(&mut fixture.context, &stake_address(&alice.pubkey(), 1)).await;
+    assert_eq!(position_0.amount, ALICE_STAKE);
+    assert_eq!(position_0.lock_until, first_lock);
+    assert_eq!(position_0.lock_duration, LOCK_30_DAYS);
+    assert_eq!(position_1.amount, 1_000);
+    assert_eq!(position_1.lock_duration, LOCK_365_DAYS);
+    assert_eq!(position_1.apy_basis_points, 15000);
+
+    let summary: StakerSummary = fetch(&mut fixture.context, &staker_summary_address(&alice.pubkey())).await;
+    assert_eq!(summary.total_amount, ALICE_STAKE + 1_000);
+    assert_eq!(summary.open_positions, 2);
+    assert_eq!(summary.next_position_index, 2);
+
+    // Closing one position leaves the other open
+    let ix = unstake_ix(&first, &alice.pubkey(), &fixture.alice_tokens, &fixture.pool_vault);
+    send(&mut fixture.context, &[ix], &[&alice]).await.unwrap();
+    assert!(fixture.context.banks_client.get_account(first).await.unwrap().is_none());
+
+    let summary: StakerSummary = fetch(&mut fixture.context, &staker_summary_address(&alice.pubkey())).await;
+    assert_eq!(summary.total_amount, 1_000);
+    assert_eq!(summary.open_positions, 1);
+}
+
+#[tokio::test]
+async fn test_crank_requires_auto_compound_opt_in() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+
+    // Alice never enabled auto-compounding, so a cranker can't touch her rewards
+    let ix = crank_compound_ix(&fixture.alice.pubkey(), &stranger.pubkey(), &fixture.stranger_tokens);
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::AutoCompoundDisabled)));
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.reward_vault).await, REWARD_FUNDING);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.stranger_tokens).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_partial_unstake_penalizes_only_withdrawn_amount() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+    send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await.unwrap();
+
+    // Still locked: the penalty is on the 400 withdrawn, the other 600 stay staked untouched
+    let penalty = quote_penalty(&mut fixture.context, &stranger.pubkey(), 400).await;
+    assert!(penalty > 0 && penalty <= 80);
+    let ix = unstake_partial_ix(&stranger.pubkey(), &fixture.stranger_tokens, 400);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+
+    let rewards = REWARD_FUNDING - token_balance(&mut fixture.context, &fixture.reward_vault).await;
+    assert_eq!(token_balance(&mut fixture.context, &fixture.stranger_tokens).await, 400 - penalty + rewards);
+
+    let position: StakeAccount = fetch(&mut fixture.context, &stake_address(&stranger.pubkey(), 0)).await;
+    assert_eq!(position.amount, 600);
+    let summary: StakerSummary = fetch(&mut fixture.context, &staker_summary_address(&stranger.pubkey())).await;
+    assert_eq!(summary.total_amount, 600);
+    assert_eq!(summary.open_positions, 1);
+
+    // More than what is left in the position
+    let ix = unstake_partial_ix(&stranger.pubkey(), &fixture.stranger_tokens, 601);
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::InvalidAmount)));
+
+    // Withdrawing the rest closes the position
+    let ix = unstake_partial_ix(&stranger.pubkey(), &fixture.stranger_tokens, 600);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+    let position = fixture.context.banks_client.get_account(stake_address(&stranger.pubkey(), 0)).await.unwrap();
+    assert!(position.is_none());
+    let summary: StakerSummary = fetch(&mut fixture.context, &staker_summary_address(&stranger.pubkey())).await;
+    assert_eq!(summary.open_positions, 0);
+}
+
+#[tokio::test]
+async fn test_config_update_timelocked_and_open_positions_keep_terms() {
+    let mut fixture = setup().await;
+    let authority = fixture.context.payer.pubkey();
+
+    // Replace all lock options with a single 7-day lock
+    let queue_ix = queue_config_update_ix(
+        &authority,
+        staking::instruction::QueueConfigUpdate {
+            lock_options: vec![LockOption {
+                duration: 7 * 86400,
+                apy_basis_points: 500,
+                weight: 5_000,
+            }],
+            staker_threshold_tokens: 5_000,
+            whale_threshold_tokens: 50_000,
+            tier_multipliers: [1, 3, 6],
+            cooldown_period: 3 * 86400,
+        },
+    );
+    send(&mut fixture.context, &[queue_ix], &[]).await.unwrap();
+
+    let execute_ix = execute_config_update_ix(&authority);
+    let result = send(&mut fixture.context, std::slice::from_ref(&execute_ix), &[]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::TimelockNotExpired)));
+
+    warp_forward(&mut fixture.context, staking::DEFAULT_TIMELOCK_DELAY).await;
+    send(&mut fixture.context, &[execute_ix], &[]).await.unwrap();
+
+    let config: PoolConfig = fetch(&mut fixture.context, &pool_config_address()).await;
+    assert_eq!(config.lock_options.len(), 1);
+    assert_eq!(config.staker_threshold, 5_000 * 10u64.pow(9));
+    assert_eq!(config.tier_multipliers, [1, 3, 6]);
+    assert_eq!(config.cooldown_period, 3 * 86400);
+
+    // Alice's 30-day position keeps the terms it was opened with
+    let position: StakeAccount = fetch(&mut fixture.context, &stake_address(&fixture.alice.pubkey(), 0)).await;
+    assert_eq!(position.lock_duration, LOCK_30_DAYS);
+    assert_eq!(position.apy_basis_points, 2000);
+    assert_eq!(position.lock_weight, 10_000);
+    assert_eq!(position.tier_multipliers, [1, 2, 5]);
+
+    // ...but new 30-day positions are no longer offered
+    let stranger = fixture.stranger.insecure_clone();
+    let result = send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::InvalidLockDuration)));
+    let ix = open_position_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000, 0, 7 * 86400);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shorter_timelock_delay_waits_out_current_one() {
+    let mut fixture = setup().await;
+    let authority = fixture.context.payer.pubkey();
+
+    let set_delay_ix = Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::SetTimelockDelay {
+            pool: pool_address(),
+            authority,
+        }
+        .to_account_metas(None),
+        data: staking::instruction::SetTimelockDelay {
+            timelock_delay: staking::MIN_TIMELOCK_DELAY,
+        }
+        .data(),
+    };
+    send(&mut fixture.context, &[set_delay_ix], &[]).await.unwrap();
+
+    // Queued right after the delay was cut, the change still waits the old 48h
+    let config: PoolConfig = fetch(&mut fixture.context, &pool_config_address()).await;
+    let queue_ix = queue_config_update_ix(
+        &authority,
+        staking::instruction::QueueConfigUpdate {
+            lock_options: config.lock_options,
+            staker_threshold_tokens: 5_000,
+            whale_threshold_tokens: 50_000,
+            tier_multipliers: config.tier_multipliers,
+            cooldown_period: config.cooldown_period,
+        },
+    );
+    send(&mut fixture.context, &[queue_ix], &[]).await.unwrap();
+
+    warp_forward(&mut fixture.context, staking::MIN_TIMELOCK_DELAY).await;
+    let result = send(&mut fixture.context, &[execute_config_update_ix(&authority)], &[]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::TimelockNotExpired)));
+
+    warp_forward(&mut fixture.context, staking::DEFAULT_TIMELOCK_DELAY - staking::MIN_TIMELOCK_DELAY).await;
+    send(&mut fixture.context, &[execute_config_update_ix(&authority)], &[]).await.unwrap();
+
+    // From then on the shorter delay applies
+    let pool: Pool = fetch(&mut fixture.context, &pool_address()).await;
+    let clock: Clock = fixture.context.banks_client.get_sysvar().await.unwrap();
+    assert_eq!(pool.timelock_delay_at(clock.unix_timestamp), staking::MIN_TIMELOCK_DELAY);
+}
+
+#[tokio::test]
+async fn test_flexible_position_cooldown() {
+    let mut fixture = setup().await;
+    let stranger = fixture.stranger.insecure_clone();
+    let owner = stranger.pubkey();
+
+    // At the flexible 0.5x lock weight a single unit would earn no weight at all
+    let ix = open_position_ix(&owner, &fixture.stranger_tokens, 1, 0, 0);
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::PositionTooSmall)));
+
+    let ix = open_position_ix(&owner, &fixture.stranger_tokens, 1_000, 0, 0);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+
+    // No lock, but it can't skip the cooldown either
+    let ix = unstake_ix(&stake_address(&owner, 0), &owner, &fixture.stranger_tokens, &pool_vault_address());
+    let result = send(&mut fixture.context, &[ix], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::CooldownRequired)));
+
+    let staked_before: Pool = fetch(&mut fixture.context, &pool_address()).await;
+    send(&mut fixture.context, &[request_unstake_ix(&owner, &fixture.stranger_tokens)], &[&stranger]).await.unwrap();
+
+    // Cooling: out of the pool and earning nothing
+    let position: StakeAccount = fetch(&mut fixture.context, &stake_address(&owner, 0)).await;
+    assert_eq!(position.status, PositionStatus::Cooling);
+    assert_eq!(position.amount, 0);
+    assert_eq!(position.weight, 0);
+    assert_eq!(position.cooling_amount, 1_000);
+    let pool: Pool = fetch(&mut fixture.context, &pool_address()).await;
+    assert_eq!(pool.total_staked, staked_before.total_staked - 1_000);
+    let summary: StakerSummary = fetch(&mut fixture.context, &staker_summary_address(&owner)).await;
+    assert_eq!(summary.open_positions, 0);
+
+    let result = send(&mut fixture.context, &[withdraw_ix(&owner, &fixture.stranger_tokens)], &[&stranger]).await;
+    assert_eq!(error_code(result), Some(u32::from(staking::ErrorCode::CooldownNotFinished)));
+
+    // Whatever was earned before the request has already been paid out
+    let balance = token_balance(&mut fixture.context, &fixture.stranger_tokens).await;
+    let config: PoolConfig = fetch(&mut fixture.context, &pool_config_address()).await;
+    warp_forward(&mut fixture.context, config.cooldown_period).await;
+    send(&mut fixture.context, &[withdraw_ix(&owner, &fixture.stranger_tokens)], &[&stranger]).await.unwrap();
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.stranger_tokens).await, balance + 1_000);
+    let position = fixture.context.banks_client.get_account(stake_address(&owner, 0)).await.unwrap();
+    assert!(position.is_none());
+}
+
+#[tokio::test]
+async fn test_short_reward_vault_pays_partially_and_records_iou() {
+    let mut fixture = setup().await;
+    let alice = fixture.alice.insecure_clone();
+    let claim = claim_ix(&alice.pubkey(), &fixture.alice_tokens, &fixture.reward_vault, &pool_authority_address());
+
+    // 60 years at 20% APY: 1.2M owed against a 1M vault
+    warp_forward(&mut fixture.context, 60 * 365 * 86400).await;
+    send(&mut fixture.context, std::slice::from_ref(&claim), &[&alice]).await.unwrap();
+
+    assert_eq!(token_balance(&mut fixture.context, &fixture.reward_vault).await, 0);
+    assert_eq!(token_balance(&mut fixture.context, &fixture.alice_tokens).await, REWARD_FUNDING);
+    let position: StakeAccount = fetch(&mut fixture.context, &stake_address(&alice.pubkey(), 0)).await;
+    assert_eq!(position.unpaid_rewards, 200_000);
+    let pool: Pool = fetch(&mut fixture.context, &pool_address()).await;
+    // The pool rounds its accrual up, at most one unit over the position's
+    assert!(pool.reward_liabilities - position.unpaid_rewards <= 1);
+    assert_eq!(pool.reward_runway(0, 60 * 365 * 86400), 0);
+
+    // A top-up is recorded and pays the IOU off on the next claim
+    let payer = fixture.context.payer.pubkey();
+    let funder_tokens = create_token_account(&mut fixture.context, &fixture.mint, &payer, 500_000).await;
+    let fund_ix = Instruction {
+        program_id: staking::ID,
+        accounts: staking::accounts::FundRewards {
+            pool: pool_address(),
+            funder: payer,
+            funder_token_account: funder_tokens,
+            reward_vault: reward_vault_address(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: staking::instruction::FundRewards { amount: 500_000 }.data(),
+    };
+    send(&mut fixture.context, &[fund_ix], &[]).await.unwrap();
+    let pool: Pool = fetch(&mut fixture.context, &pool_address()).await;
+    assert_eq!(pool.total_reward_funded, 500_000);
+
+    warp_forward(&mut fixture.context, 1).await;
+    send(&mut fixture.context, &[claim], &[&alice]).await.unwrap();
+
+    let position: StakeAccount = fetch(&mut fixture.context, &stake_address(&alice.pubkey(), 0)).await;
+    assert_eq!(position.unpaid_rewards, 0);
+    assert!(token_balance(&mut fixture.context, &fixture.alice_tokens).await >= REWARD_FUNDING + 200_000);
+}
+
+#[tokio::test]
+async fn test_stake_and_unstake_emit_events() {
+    let mut fixture = setup().await;
+    record_program_logs();
+    let stranger = fixture.stranger.insecure_clone();
+
+    send(&mut fixture.context, &[stake_ix(&stranger.pubkey(), &fixture.stranger_tokens, 1_000)], &[&stranger]).await.unwrap();
+    let ix = unstake_ix(&stake_address(&stranger.pubkey(), 0), &stranger.pubkey(), &fixture.stranger_tokens, &fixture.pool_vault);
+    send(&mut fixture.context, &[ix], &[&stranger]).await.unwrap();
+
+    // Other tests run in parallel, so only look at the stranger's events
+    let logs = program_logs();
+    let staked: Vec<Staked> = decode_events::<Staked>(&logs).into_iter().filter(|event| event.owner == stranger.pubkey()).collect();
+    assert_eq!(staked.len(), 1);
+    assert_eq!(staked[0].position_index, 0);
+    assert_eq!(staked[0].amount, 1_000);
+    assert_eq!(staked[0].total_amount, 1_000);
+    assert_eq!(staked[0].pool_total_staked, ALICE_STAKE + 1_000);
+
+    let unstaked: Vec<Unstaked> = decode_events::<Unstaked>(&logs).into_iter().filter(|event| event.owner == stranger.pubkey()).collect();
+    assert_eq!(unstaked.len(), 1);
+    assert_eq!(unstaked[0].amount, 1_000);
+    assert_eq!(unstaked[0].received, 1_000 - unstaked[0].penalty + unstaked[0].rewards + unstaked[0].penalty_share);
+    assert_eq!(unstaked[0].remaining, 0);
+    assert_eq!(
+        token_balance(&mut fixture.context, &fixture.stranger_tokens).await,
+        unstaked[0].received
+    );
+}